@@ -0,0 +1,284 @@
+//!
+//! # Arena-Based Binary Tree
+//!
+//! An alternative to the pointer-based [BinaryTree], with the same `insert(data, parent)` semantics.
+//! Nodes live in a single vector, and refer to one another by [NodeId] index.
+//! This saves an allocation and a reference-count per node,
+//! and replaces the run-time borrow-checks of `RefCell` with ordinary compile-time ones.
+//!
+//! Nodes are never removed, so each [NodeId] remains valid for the life of its tree.
+//! Read-only operations, including traversals, are provided by [TreeView].
+//!
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+// Local Imports
+use crate::binary_tree::{level_order_tokens, BinaryTree, Error};
+use crate::tree_view::TreeView;
+
+///
+/// # Node Index
+///
+/// Reference to a node of an [ArenaTree]
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// Arena Binary Tree Node
+#[derive(Debug)]
+pub struct Node<T> {
+    pub data: T,
+    pub left: Option<NodeId>,
+    pub right: Option<NodeId>,
+}
+
+///
+/// # Arena Binary Tree
+///
+#[derive(Debug)]
+pub struct ArenaTree<T> {
+    nodes: Vec<Node<T>>,
+    head: Option<NodeId>,
+}
+impl<T> Default for ArenaTree<T> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            head: None,
+        }
+    }
+}
+impl<T> Index<NodeId> for ArenaTree<T> {
+    type Output = Node<T>;
+    fn index(&self, id: NodeId) -> &Node<T> {
+        &self.nodes[id.0]
+    }
+}
+impl<T> IndexMut<NodeId> for ArenaTree<T> {
+    fn index_mut(&mut self, id: NodeId) -> &mut Node<T> {
+        &mut self.nodes[id.0]
+    }
+}
+impl<T> ArenaTree<T> {
+    /// Create a new, empty tree
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Insert a new element with value `data`.
+    /// At the end of `insert`, the new element is always childless.
+    /// It is appended as a child of `parent` if a slot is available.
+    /// If a `parent` is provided, but has neither child available, returns an `Err`.
+    /// If no `parent` is provided, and the tree has an existing `head`, also returns an `Err`.
+    /// A `parent` out of range of this tree's nodes, e.g. one from a larger tree, also returns an `Err`.
+    pub fn insert(&mut self, data: T, parent: Option<NodeId>) -> Result<NodeId, Error> {
+        let id = NodeId(self.nodes.len());
+        match parent {
+            Some(p) => {
+                let pnode = self.nodes.get_mut(p.0).ok_or(Error)?;
+                if pnode.left.is_none() {
+                    pnode.left = Some(id);
+                } else if pnode.right.is_none() {
+                    pnode.right = Some(id);
+                } else {
+                    return Err(Error); // Parent with no child-slots available. Fail.
+                }
+            }
+            None => match self.head {
+                Some(_) => return Err(Error), // No parent, and `head` taken. Fail.
+                None => self.head = Some(id),
+            },
+        }
+        self.nodes.push(Node {
+            data,
+            left: None,
+            right: None,
+        });
+        Ok(id)
+    }
+    /// Get the head node, if any
+    pub fn head(&self) -> Option<NodeId> {
+        self.head
+    }
+    /// Get the data of node `id`
+    pub fn get(&self, id: NodeId) -> &T {
+        &self[id].data
+    }
+    /// Get a mutable reference to the data of node `id`
+    pub fn get_mut(&mut self, id: NodeId) -> &mut T {
+        &mut self[id].data
+    }
+}
+impl<T: FromStr> ArenaTree<T> {
+    /// Parse level-order text, as for [BinaryTree::deserialize], directly into the arena.
+    /// Nodes are numbered in level-order.
+    pub fn deserialize(src: &str) -> Result<Self, Error> {
+        let mut tokens = level_order_tokens(src)?.into_iter();
+        let mut tree = Self::default();
+        tree.head = match tokens.next() {
+            Some(token) => tree.parse_node(token)?,
+            None => return Ok(tree),
+        };
+        // Nodes are pushed in level-order, so those awaiting children are always the next in line
+        let mut next = 0;
+        while next < tree.nodes.len() {
+            let id = NodeId(next);
+            tree[id].left = tokens
+                .next()
+                .map(|t| tree.parse_node(t))
+                .transpose()?
+                .flatten();
+            tree[id].right = tokens
+                .next()
+                .map(|t| tree.parse_node(t))
+                .transpose()?
+                .flatten();
+            next += 1;
+        }
+        if tokens.next().is_some() {
+            return Err(Error); // Elements without a parent
+        }
+        Ok(tree)
+    }
+    /// Parse a single element into a new, un-linked node. Returns `None` for `null`.
+    fn parse_node(&mut self, token: &str) -> Result<Option<NodeId>, Error> {
+        if token == "null" {
+            return Ok(None);
+        }
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Node {
+            data: token.parse().map_err(|_| Error)?,
+            left: None,
+            right: None,
+        });
+        Ok(Some(id))
+    }
+}
+impl<T: Clone> From<&BinaryTree<T>> for ArenaTree<T> {
+    /// Convert from a pointer-based [BinaryTree] of the same shape, holding copies of its data.
+    /// Nodes are numbered in pre-order.
+    fn from(tree: &BinaryTree<T>) -> Self {
+        let mut arena = Self::default();
+        // Iterative pre-order walk. Each stack entry is a node to copy, and the parent-slot to link it into.
+        let mut stack: Vec<_> = tree.head.iter().map(|h| (h.clone(), None)).collect();
+        while let Some((ptr, slot)) = stack.pop() {
+            let node = ptr.borrow();
+            let id = NodeId(arena.nodes.len());
+            arena.nodes.push(Node {
+                data: node.data.clone(),
+                left: None,
+                right: None,
+            });
+            match slot {
+                None => arena.head = Some(id),
+                Some((parent, Side::Left)) => arena[parent].left = Some(id),
+                Some((parent, Side::Right)) => arena[parent].right = Some(id),
+            }
+            // Push the right child first, so that the left is copied next
+            if let Some(right) = &node.right {
+                stack.push((right.clone(), Some((id, Side::Right))));
+            }
+            if let Some(left) = &node.left {
+                stack.push((left.clone(), Some((id, Side::Left))));
+            }
+        }
+        arena
+    }
+}
+/// Child-slot of a parent node
+#[derive(Debug, Clone, Copy)]
+enum Side {
+    Left,
+    Right,
+}
+impl<T> TreeView for ArenaTree<T> {
+    type Data = T;
+    type Node = NodeId;
+    type DataRef<'a>
+        = &'a T
+    where
+        T: 'a;
+    fn root(&self) -> Option<NodeId> {
+        self.head
+    }
+    fn left(&self, node: &NodeId) -> Option<NodeId> {
+        self[*node].left
+    }
+    fn right(&self, node: &NodeId) -> Option<NodeId> {
+        self[*node].right
+    }
+    fn data<'a>(&'a self, node: &'a NodeId) -> &'a T {
+        &self[*node].data
+    }
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+}
+/// ASCII-art rendering, as for [TreeView::render]
+impl<T: fmt::Display> fmt::Display for ArenaTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+#[test]
+fn test_arena_tree() -> Result<(), Error> {
+    let mut tree = ArenaTree::new();
+    let n1 = tree.insert(1, None)?;
+    let n2 = tree.insert(2, Some(n1))?;
+    let n3 = tree.insert(3, Some(n1))?;
+    let n4 = tree.insert(4, Some(n2))?;
+    tree.insert(5, Some(n3))?;
+    assert_eq!(tree.insert(0, None), Err(Error));
+    assert_eq!(tree.insert(0, Some(n1)), Err(Error));
+    assert_eq!(tree[n2].left, Some(n4));
+    *tree.get_mut(n4) += 10;
+    assert_eq!(*tree.get(n4), 14);
+
+    let data = |nodes: Vec<NodeId>| -> Vec<i32> { nodes.iter().map(|&n| tree[n].data).collect() };
+    assert_eq!(data(tree.iter_preorder().collect()), [1, 2, 14, 3, 5]);
+    assert_eq!(data(tree.iter_inorder().collect()), [14, 2, 1, 5, 3]);
+    assert_eq!(data(tree.iter_postorder().collect()), [14, 2, 5, 3, 1]);
+    assert_eq!(data(tree.leaves().collect()), [14, 5]);
+    assert_eq!((tree.len(), tree.depth()), (5, 3));
+    assert_eq!(tree.serialize(), "[1,2,3,14,null,5]");
+
+    // Out-of-range parents are rejected
+    let mut small = ArenaTree::new();
+    small.insert(0, None)?;
+    assert_eq!(small.insert(0, Some(n4)), Err(Error));
+    Ok(())
+}
+
+#[test]
+fn test_from_binary_tree() {
+    let src = "[1,2,3,null,4,5,null,6]";
+    let ptrs: BinaryTree<i32> = BinaryTree::deserialize(src).unwrap();
+    let arena = ArenaTree::from(&ptrs);
+    assert_eq!(arena.serialize(), src);
+    assert_eq!(arena.to_string(), ptrs.to_string());
+    assert_eq!(arena.len(), ptrs.len());
+
+    let arena: ArenaTree<i32> = ArenaTree::deserialize(src).unwrap();
+    assert_eq!(arena.serialize(), src);
+    assert!(ArenaTree::<i32>::from(&BinaryTree::default()).is_empty());
+    assert!(ArenaTree::<i32>::deserialize("[]").unwrap().is_empty());
+    assert!(ArenaTree::<i32>::deserialize("[1,null,null,2]").is_err());
+
+    // Parsing needs no `Clone`, nor any intermediate [BinaryTree]
+    #[derive(Debug)]
+    struct Label(String);
+    impl FromStr for Label {
+        type Err = ();
+        fn from_str(s: &str) -> Result<Self, ()> {
+            Ok(Label(s.to_string()))
+        }
+    }
+    let arena: ArenaTree<Label> = ArenaTree::deserialize("[a, b, c, null, d]").unwrap();
+    let labels: Vec<&str> = arena
+        .iter_preorder()
+        .map(|n| arena[n].data.0.as_str())
+        .collect();
+    assert_eq!(labels, ["a", "b", "d", "c"]);
+}
@@ -1,27 +1,52 @@
 //!
 //! # Binary Search Tree
 //!
+//! Index-based binary search trees, with parent pointers.
+//! [BstMap] is a generic ordered map. [BinarySearchTree] is a thin wrapper, a set of `isize` values.
+//!
+//! Both are parameterized by a [Balance] strategy, which defaults to [Unbalanced].
+//! The self-balancing [Avl] and [RedBlack] strategies share the same storage and API,
+//! and are available via aliases [AvlMap] and [RedBlackMap].
+//! [BstMap] implements [TreeView], for use with the tree algorithms of chapter 4.
+//!
 
-use std::ops::{Index, IndexMut};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::ops::{Bound, Deref, Index, IndexMut, RangeBounds};
+
+// Local Imports
+use crate::binary_tree::{self, BinaryTree, NodePtr};
+use crate::chapter_04::p04_check_balanced::check_balanced;
+use crate::chapter_04::p05_validate_bst::validate_bst;
+use crate::tree_view::TreeView;
 
 ///
 /// # Another Binary Tree Node!
 ///
 /// This time with a parent pointer, and using index-references.
+/// Field `size` counts the nodes in its sub-tree, including itself, and supports order-statistic queries.
+/// Field `meta` holds any per-node data of the tree's [Balance] strategy.
 ///
 #[derive(Debug)]
-pub struct Node {
-    pub data: isize,
+pub struct Node<K, V, M = ()> {
+    pub key: K,
+    pub value: V,
+    pub size: usize,
+    pub meta: M,
     pub index: NodeIndex,
     pub left: Option<NodeIndex>,
     pub right: Option<NodeIndex>,
     pub parent: Option<NodeIndex>,
 }
-impl Node {
+impl<K, V, M> Node<K, V, M> {
     /// Create a new [Node] with no children, and no parent
-    pub fn orphan(data: isize, index: NodeIndex) -> Self {
+    pub fn orphan(key: K, value: V, meta: M, index: NodeIndex) -> Self {
         Self {
-            data,
+            key,
+            value,
+            size: 1,
+            meta,
             index,
             left: None,
             right: None,
@@ -31,130 +56,1688 @@ impl Node {
 }
 
 /// Index Reference into a Tree
-#[derive(Debug, Clone, Copy)]
-pub struct NodeIndex(usize);
+///
+/// Removing nodes compacts the tree's storage, moving its last node into the vacated slot.
+/// Indices held across a removal may therefore be invalidated.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeIndex(pub(crate) usize);
 
-/// Binary Tree
-#[derive(Debug, Default)]
-pub struct BinarySearchTree {
-    pub nodes: Vec<Node>,
+///
+/// # Binary Search Tree Map
+///
+/// Ordered map from keys `K` to values `V`.
+/// Nodes are stored contiguously in `nodes`, and linked by [NodeIndex].
+/// Balance is maintained by strategy `B`, which defaults to [Unbalanced].
+///
+/// Augmented trees, such as [crate::interval_tree], may also keep per-node data which depends on the
+/// tree's shape, via an `augment` callback. It is re-run wherever sub-tree sizes change:
+/// along the path of each insertion and removal, and on both nodes of each rotation.
+///
+#[derive(Debug)]
+pub struct BstMap<K, V, B: Balance = Unbalanced> {
+    pub nodes: Vec<Node<K, V, B::Meta>>,
     pub head: Option<NodeIndex>,
+    augment: fn(&mut Self, NodeIndex),
+}
+/// Self-Balancing AVL Tree Map
+pub type AvlMap<K, V> = BstMap<K, V, Avl>;
+/// Self-Balancing Red-Black Tree Map
+pub type RedBlackMap<K, V> = BstMap<K, V, RedBlack>;
+
+impl<K, V> Default for BstMap<K, V> {
+    fn default() -> Self {
+        Self::empty()
+    }
 }
-impl Index<NodeIndex> for BinarySearchTree {
-    type Output = Node;
-    fn index(&self, index: NodeIndex) -> &Node {
+impl<K, V, B: Balance> Index<NodeIndex> for BstMap<K, V, B> {
+    type Output = Node<K, V, B::Meta>;
+    fn index(&self, index: NodeIndex) -> &Node<K, V, B::Meta> {
         &self.nodes[index.0]
     }
 }
-impl IndexMut<NodeIndex> for BinarySearchTree {
-    fn index_mut(&mut self, index: NodeIndex) -> &mut Node {
+impl<K, V, B: Balance> IndexMut<NodeIndex> for BstMap<K, V, B> {
+    fn index_mut(&mut self, index: NodeIndex) -> &mut Node<K, V, B::Meta> {
         &mut self.nodes[index.0]
     }
 }
+impl<K, V, B: Balance> TreeView for BstMap<K, V, B> {
+    type Data = K;
+    type Node = NodeIndex;
+    type DataRef<'a>
+        = &'a K
+    where
+        Self: 'a;
+    fn root(&self) -> Option<NodeIndex> {
+        self.head
+    }
+    fn left(&self, node: &NodeIndex) -> Option<NodeIndex> {
+        self[*node].left
+    }
+    fn right(&self, node: &NodeIndex) -> Option<NodeIndex> {
+        self[*node].right
+    }
+    fn data<'a>(&'a self, node: &'a NodeIndex) -> &'a K {
+        &self[*node].key
+    }
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+}
 
-impl BinarySearchTree {
-    /// Insert a new [Node] with value `val`
-    pub fn insert(&mut self, val: isize) {
-        // Create and add the new node
-        let newindex = self.add_node(val);
-
-        let mut parent = match self.head {
-            Some(p) => p,
-            None => {
-                // First node. Make it our `head` pointer.
-                return self.head = Some(newindex);
+impl<K: Ord, V> BstMap<K, V> {
+    /// Create a new, empty, [Unbalanced] map. Also available via [Default].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl<K: Ord, V> BstMap<K, V, Avl> {
+    /// Create a new, empty, [Avl]-balanced map
+    pub fn new_avl() -> Self {
+        Self::empty()
+    }
+}
+impl<K: Ord, V> BstMap<K, V, RedBlack> {
+    /// Create a new, empty, [RedBlack]-balanced map
+    pub fn new_red_black() -> Self {
+        Self::empty()
+    }
+}
+impl<K: Ord, V, B: Balance> BstMap<K, V, B> {
+    /// Insert `value` at `key`.
+    /// Returns the previous value at `key`, if one was present.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut e) => Some(e.insert(value)),
+            Entry::Vacant(e) => {
+                e.insert(value);
+                None
             }
-        };
+        }
+    }
+    /// Get a reference to the value at `key`, if present
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).map(|idx| &self[idx].value)
+    }
+    /// Get a mutable reference to the value at `key`, if present.
+    /// Notifies the [Balance] strategy of the last node searched, whether or not it holds `key`.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (idx, found) = self.search_path(key)?;
+        B::accessed(self, idx);
+        found.then(move || &mut self[idx].value)
+    }
+    /// Boolean indication of whether `key` is present
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+    /// Get the [Entry] for `key`, for in-place insertion or update
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, B> {
+        match self.search(&key) {
+            Ok(index) => {
+                B::accessed(self, index);
+                Entry::Occupied(OccupiedEntry { map: self, index })
+            }
+            Err(parent) => Entry::Vacant(VacantEntry {
+                map: self,
+                key,
+                parent,
+            }),
+        }
+    }
+    /// Remove `key`, returning its value if present
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (idx, found) = self.search_path(key)?;
+        if !found {
+            B::accessed(self, idx);
+            return None;
+        }
+        Some(self.remove_node(idx).1)
+    }
+    /// Find the node with key `key`, if present
+    pub fn find<Q>(&self, key: &Q) -> Option<NodeIndex>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut node = self.head;
+        while let Some(idx) = node {
+            node = match key.cmp(self[idx].key.borrow()) {
+                Ordering::Equal => return Some(idx),
+                Ordering::Less => self[idx].left,
+                Ordering::Greater => self[idx].right,
+            };
+        }
+        None // Not found
+    }
+    /// Walk the search path for `key`.
+    /// Returns the last node visited, and a boolean indication of whether it holds `key`,
+    /// or `None` if the map is empty.
+    pub(crate) fn search_path<Q>(&self, key: &Q) -> Option<(NodeIndex, bool)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut last = self.head?;
         loop {
-            if self[parent].data > val {
-                // Look left
-                match self[parent].left {
-                    Some(idx) => {
-                        if self[idx].data < val {
-                            // Node goes here, as `parent`s left and with `idx` as its left sub-tree
-                            self[newindex].parent = Some(parent);
-                            self[parent].left = Some(newindex);
-                            self[newindex].left = Some(idx);
-                            self[idx].parent = Some(newindex);
-                            break;
-                        } else {
-                            parent = idx;
-                            continue;
-                        }
-                    }
-                    None => {
-                        self[newindex].parent = Some(parent);
-                        self[parent].left = Some(newindex);
-                        break;
-                    }
+            let next = match key.cmp(self[last].key.borrow()) {
+                Ordering::Equal => return Some((last, true)),
+                Ordering::Less => self[last].left,
+                Ordering::Greater => self[last].right,
+            };
+            match next {
+                Some(idx) => last = idx,
+                None => return Some((last, false)),
+            }
+        }
+    }
+    /// Search for `key`.
+    /// Returns its node if found, or otherwise the parent and side at which it would be inserted.
+    fn search(&self, key: &K) -> Result<NodeIndex, Option<(NodeIndex, Ordering)>> {
+        let mut parent = None;
+        let mut node = self.head;
+        while let Some(idx) = node {
+            let ord = key.cmp(&self[idx].key);
+            node = match ord {
+                Ordering::Equal => return Ok(idx),
+                Ordering::Less => self[idx].left,
+                Ordering::Greater => self[idx].right,
+            };
+            parent = Some((idx, ord));
+        }
+        Err(parent)
+    }
+    /// Iterate over entries with keys in `range`, in ascending key-order.
+    /// Begins from the first in-range node, found in O(height) time, without visiting those before it.
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V, B>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let front = self.lower_bound(range.start_bound());
+        let back = self.upper_bound(range.end_bound());
+        match (front, back) {
+            (Some(f), Some(b)) if self[f].key <= self[b].key => Iter {
+                tree: self,
+                front,
+                back,
+            },
+            _ => Iter {
+                tree: self,
+                front: None,
+                back: None,
+            },
+        }
+    }
+    /// Get the rank of `key`: the number of keys strictly less than it.
+    /// Runs in O(height) time, using sub-tree sizes. `key` need not be present.
+    pub fn rank<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.count_less(key, false)
+    }
+    /// Get the node with the `k`th smallest key, counting from zero.
+    /// Returns `None` if `k` is out of bounds.
+    pub fn select(&self, mut k: usize) -> Option<NodeIndex> {
+        let mut node = self.head;
+        while let Some(idx) = node {
+            let left = self.size_of(self[idx].left);
+            node = match k.cmp(&left) {
+                Ordering::Equal => return Some(idx),
+                Ordering::Less => self[idx].left,
+                Ordering::Greater => {
+                    // Skip the left sub-tree and `idx` itself
+                    k -= left + 1;
+                    self[idx].right
                 }
+            };
+        }
+        None
+    }
+    /// Count the keys in `range`, in O(height) time
+    pub fn count_range<Q, R>(&self, range: R) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        // Count those before the start, and those up to the end
+        let before = match range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Included(a) => self.count_less(a, false),
+            Bound::Excluded(a) => self.count_less(a, true),
+        };
+        let through = match range.end_bound() {
+            Bound::Unbounded => self.len(),
+            Bound::Included(b) => self.count_less(b, true),
+            Bound::Excluded(b) => self.count_less(b, false),
+        };
+        through.saturating_sub(before)
+    }
+    /// Get the lower median: the middle node if the map has an odd number of entries,
+    /// or the smaller of the two middle nodes if even. Returns `None` if empty.
+    pub fn median_low(&self) -> Option<NodeIndex> {
+        self.select(self.len().checked_sub(1)? / 2)
+    }
+    /// Get the upper median: the middle node if the map has an odd number of entries,
+    /// or the larger of the two middle nodes if even. Returns `None` if empty.
+    pub fn median_high(&self) -> Option<NodeIndex> {
+        self.select(self.len() / 2)
+    }
+    /// Count the keys less than `key`, or less than or equal to it if `inclusive`
+    fn count_less<Q>(&self, key: &Q, inclusive: bool) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut count = 0;
+        let mut node = self.head;
+        while let Some(idx) = node {
+            let counted = match self[idx].key.borrow().cmp(key) {
+                Ordering::Less => true,
+                Ordering::Equal => inclusive,
+                Ordering::Greater => false,
+            };
+            node = if counted {
+                // Count `idx` and its left sub-tree, and continue to the right
+                count += self.size_of(self[idx].left) + 1;
+                self[idx].right
             } else {
-                // Look right
-                match self[parent].right {
-                    Some(idx) => {
-                        if self[idx].data > val {
-                            // Node goes here, as `parent`s right and with `idx` as its right sub-tree
-                            self[newindex].parent = Some(parent);
-                            self[parent].right = Some(newindex);
-                            self[newindex].right = Some(idx);
-                            self[idx].parent = Some(newindex);
-                            break;
-                        } else {
-                            parent = idx;
-                            continue;
-                        }
-                    }
-                    None => {
-                        self[newindex].parent = Some(parent);
-                        self[parent].right = Some(newindex);
-                        break;
-                    }
-                }
+                self[idx].left
+            };
+        }
+        count
+    }
+    /// Find the first node with a key satisfying lower-bound `bound`, if any
+    fn lower_bound<Q>(&self, bound: Bound<&Q>) -> Option<NodeIndex>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut best = None;
+        let mut node = self.head;
+        while let Some(idx) = node {
+            let key = self[idx].key.borrow();
+            let in_bounds = match bound {
+                Bound::Unbounded => true,
+                Bound::Included(b) => key >= b,
+                Bound::Excluded(b) => key > b,
+            };
+            // If in bounds, this is our best so far, but a smaller one may lie to the left
+            node = if in_bounds {
+                best = Some(idx);
+                self[idx].left
+            } else {
+                self[idx].right
+            };
+        }
+        best
+    }
+    /// Find the last node with a key satisfying upper-bound `bound`, if any
+    fn upper_bound<Q>(&self, bound: Bound<&Q>) -> Option<NodeIndex>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        // Mirror image of `lower_bound`
+        let mut best = None;
+        let mut node = self.head;
+        while let Some(idx) = node {
+            let key = self[idx].key.borrow();
+            let in_bounds = match bound {
+                Bound::Unbounded => true,
+                Bound::Included(b) => key <= b,
+                Bound::Excluded(b) => key < b,
+            };
+            node = if in_bounds {
+                best = Some(idx);
+                self[idx].right
+            } else {
+                self[idx].left
+            };
+        }
+        best
+    }
+    /// Debug check of all invariants: binary-search ordering, link consistency,
+    /// and those of balance-strategy `B`.
+    /// Ordering and balance checks are those of [validate_bst] and [check_balanced].
+    pub fn check_invariants(&self) -> bool
+    where
+        K: Clone,
+    {
+        validate_bst(self) && self.check_links() && B::check(self)
+    }
+}
+impl<K, V, B: Balance> BstMap<K, V, B> {
+    /// Create a new, empty map
+    pub(crate) fn empty() -> Self {
+        Self::augmented(|_, _| {})
+    }
+    /// Create a new, empty map, re-computing each node's augmented data with `augment`.
+    /// The callback may read any node's key and value, but should only write to node `idx` and its parent.
+    pub(crate) fn augmented(augment: fn(&mut Self, NodeIndex)) -> Self {
+        Self {
+            nodes: Vec::new(),
+            head: None,
+            augment,
+        }
+    }
+    /// Iterate over all entries, in ascending key-order.
+    /// Lazily walks the `parent` links, in O(1) space.
+    pub fn iter(&self) -> Iter<'_, K, V, B> {
+        Iter {
+            tree: self,
+            front: self.min(),
+            back: self.max(),
+        }
+    }
+    /// Iterate over all keys, in ascending order
+    pub fn keys(&self) -> Keys<'_, K, V, B> {
+        Keys(self.iter())
+    }
+    /// Iterate over all values, in ascending key-order
+    pub fn values(&self) -> Values<'_, K, V, B> {
+        Values(self.iter())
+    }
+    /// Get the number of entries in the map
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+    /// Boolean indication of whether the map is empty
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+    /// Get the node with the minimum key, if any
+    pub fn min(&self) -> Option<NodeIndex> {
+        self.head.map(|h| self.leftmost(h))
+    }
+    /// Get the node with the maximum key, if any
+    pub fn max(&self) -> Option<NodeIndex> {
+        self.head.map(|h| self.rightmost(h))
+    }
+    /// Get the left-most descendant of `idx`, i.e. the minimum of its sub-tree
+    pub fn leftmost(&self, mut idx: NodeIndex) -> NodeIndex {
+        while let Some(left) = self[idx].left {
+            idx = left;
+        }
+        idx
+    }
+    /// Get the right-most descendant of `idx`, i.e. the maximum of its sub-tree
+    pub fn rightmost(&self, mut idx: NodeIndex) -> NodeIndex {
+        while let Some(right) = self[idx].right {
+            idx = right;
+        }
+        idx
+    }
+    /// Get the in-order successor of node `idx`, if any
+    pub fn successor(&self, mut idx: NodeIndex) -> Option<NodeIndex> {
+        // If `idx` has a right sub-tree, the successor is its leftmost element
+        if let Some(right) = self[idx].right {
+            return Some(self.leftmost(right));
+        }
+        // Otherwise it is the first ancestor which we reach coming up from the left
+        while let Some(parent) = self[idx].parent {
+            if self[parent].left == Some(idx) {
+                return Some(parent);
+            }
+            idx = parent;
+        }
+        None // Reached the head. `idx` is the maximum.
+    }
+    /// Get the in-order predecessor of node `idx`, if any
+    pub fn predecessor(&self, mut idx: NodeIndex) -> Option<NodeIndex> {
+        // Mirror image of `successor`
+        if let Some(left) = self[idx].left {
+            return Some(self.rightmost(left));
+        }
+        while let Some(parent) = self[idx].parent {
+            if self[parent].right == Some(idx) {
+                return Some(parent);
             }
+            idx = parent;
         }
+        None // Reached the head. `idx` is the minimum.
+    }
+    /// Remove node `idx` from the tree, returning its key and value.
+    ///
+    /// Storage remains compact: the last node in `nodes` moves into the vacated slot,
+    /// and any [NodeIndex] referring to it is invalidated.
+    pub fn remove_node(&mut self, idx: NodeIndex) -> (K, V) {
+        // A node with two children trades entries with its successor, which is then removed instead.
+        // The successor is the leftmost of its right sub-tree, so has no left child.
+        let idx = match (self[idx].left, self[idx].right) {
+            (Some(_), Some(right)) => {
+                let succ = self.leftmost(right);
+                self.swap_entries(idx, succ);
+                succ
+            }
+            _ => idx,
+        };
+        // Now `idx` has at most one child. Splice it out, linking that child to its parent.
+        let child = self[idx].left.or(self[idx].right);
+        let parent = self[idx].parent;
+        if let Some(c) = child {
+            self[c].parent = parent;
+        }
+        self.replace_child(parent, idx, child);
+        self.adjust_sizes(parent, -1);
+
+        // Re-balance, and free its slot
+        let meta = self[idx].meta;
+        B::removed(self, parent, child, meta);
+        self.swap_remove(idx)
+    }
+    /// Convert to a pointer-based [BinaryTree] of the same shape, holding copies of our keys
+    pub fn to_binary_tree(&self) -> BinaryTree<K>
+    where
+        K: Clone,
+    {
+        BinaryTree {
+            head: self.head.map(|h| self.to_binary_node(h)),
+        }
+    }
+    /// Recursive helper for `to_binary_tree`
+    fn to_binary_node(&self, idx: NodeIndex) -> NodePtr<K>
+    where
+        K: Clone,
+    {
+        let left = self[idx].left.map(|l| self.to_binary_node(l));
+        let right = self[idx].right.map(|r| self.to_binary_node(r));
+        NodePtr::new(binary_tree::Node::new(self[idx].key.clone(), left, right))
+    }
+    /// Check that all parent, child, and index links are mutually consistent,
+    /// and that all sub-tree sizes are accurate
+    fn check_links(&self) -> bool {
+        self.nodes.iter().enumerate().all(|(pos, node)| {
+            let children_ok = [node.left, node.right]
+                .iter()
+                .flatten()
+                .all(|&c| self[c].parent == Some(node.index));
+            let parent_ok = match node.parent {
+                None => self.head == Some(node.index),
+                Some(p) => self[p].left == Some(node.index) || self[p].right == Some(node.index),
+            };
+            let size_ok = node.size == 1 + self.size_of(node.left) + self.size_of(node.right);
+            node.index == NodeIndex(pos) && children_ok && parent_ok && size_ok
+        })
+    }
+    /// Get the size of optional sub-tree `opt`
+    pub(crate) fn size_of(&self, opt: Option<NodeIndex>) -> usize {
+        opt.map_or(0, |idx| self[idx].size)
     }
-    /// Add a new [Node] with value `data`. Returns its index.
-    fn add_node(&mut self, data: isize) -> NodeIndex {
+    /// Recompute the size of `idx` from those of its children
+    pub(crate) fn update_size(&mut self, idx: NodeIndex) {
+        self[idx].size = 1 + self.size_of(self[idx].left) + self.size_of(self[idx].right);
+    }
+    /// Add `delta` to the size of `start` and each of its ancestors, re-augmenting each
+    fn adjust_sizes(&mut self, start: Option<NodeIndex>, delta: isize) {
+        let mut node = start;
+        while let Some(idx) = node {
+            self[idx].size = (self[idx].size as isize + delta) as usize;
+            self.augment_node(idx);
+            node = self[idx].parent;
+        }
+    }
+    /// Re-compute the augmented data of node `idx`, from its own entry and its children's
+    fn augment_node(&mut self, idx: NodeIndex) {
+        (self.augment)(self, idx)
+    }
+    /// Add a new, un-linked [Node]. Returns its index.
+    fn add_node(&mut self, key: K, value: V) -> NodeIndex {
         let index = NodeIndex(self.nodes.len());
-        self.nodes.push(Node {
-            data,
-            index,
-            parent: None,
-            left: None,
-            right: None,
-        });
+        self.nodes.push(Node::orphan(key, value, B::leaf(), index));
         index
     }
-    /// In-order traversal, creating and returning a vector of data-elements
-    pub fn inorder(&self) -> Vec<isize> {
-        let mut v = Vec::new();
-        self._inorder(&self.head, &mut v);
-        v
+    /// Rotate the sub-tree at `idx` leftward, lifting its right child into its place.
+    /// Returns the new sub-tree root. Panics if `idx` has no right child.
+    pub(crate) fn rotate_left(&mut self, idx: NodeIndex) -> NodeIndex {
+        let pivot = self[idx].right.unwrap();
+        let inner = self[pivot].left;
+        self[idx].right = inner;
+        if let Some(i) = inner {
+            self[i].parent = Some(idx);
+        }
+        let parent = self[idx].parent;
+        self[pivot].parent = parent;
+        self.replace_child(parent, idx, Some(pivot));
+        self[pivot].left = Some(idx);
+        self[idx].parent = Some(pivot);
+        self[pivot].size = self[idx].size;
+        self.update_size(idx);
+        self.augment_node(idx);
+        self.augment_node(pivot);
+        pivot
     }
-    /// Recursive helper for `inorder`
-    fn _inorder(&self, opt: &Option<NodeIndex>, v: &mut Vec<isize>) {
-        match opt {
-            None => (),
-            Some(idx) => {
-                self._inorder(&self[*idx].left, v);
-                v.push(self[*idx].data);
-                self._inorder(&self[*idx].right, v);
+    /// Rotate the sub-tree at `idx` rightward, lifting its left child into its place.
+    /// Returns the new sub-tree root. Panics if `idx` has no left child.
+    pub(crate) fn rotate_right(&mut self, idx: NodeIndex) -> NodeIndex {
+        let pivot = self[idx].left.unwrap();
+        let inner = self[pivot].right;
+        self[idx].left = inner;
+        if let Some(i) = inner {
+            self[i].parent = Some(idx);
+        }
+        let parent = self[idx].parent;
+        self[pivot].parent = parent;
+        self.replace_child(parent, idx, Some(pivot));
+        self[pivot].right = Some(idx);
+        self[idx].parent = Some(pivot);
+        self[pivot].size = self[idx].size;
+        self.update_size(idx);
+        self.augment_node(idx);
+        self.augment_node(pivot);
+        pivot
+    }
+    /// Swap the keys and values of nodes `a` and `b`, leaving their links in place
+    fn swap_entries(&mut self, a: NodeIndex, b: NodeIndex) {
+        let (lo, hi) = (a.0.min(b.0), a.0.max(b.0));
+        if lo == hi {
+            return;
+        }
+        let (first, rest) = self.nodes.split_at_mut(hi);
+        std::mem::swap(&mut first[lo].key, &mut rest[0].key);
+        std::mem::swap(&mut first[lo].value, &mut rest[0].value);
+    }
+    /// Replace `parent`s link to child `old` with `new`.
+    /// A `parent` of `None` indicates that `old` is the head.
+    pub(crate) fn replace_child(
+        &mut self,
+        parent: Option<NodeIndex>,
+        old: NodeIndex,
+        new: Option<NodeIndex>,
+    ) {
+        match parent {
+            None => self.head = new,
+            Some(p) => {
+                if self[p].left == Some(old) {
+                    self[p].left = new;
+                } else {
+                    self[p].right = new;
+                }
+            }
+        }
+    }
+    /// Remove the (already un-linked) node at `idx` from storage, returning its entry.
+    /// Moves the last node into its slot, and re-points all links to it.
+    fn swap_remove(&mut self, idx: NodeIndex) -> (K, V) {
+        let node = self.take_node(idx);
+        (node.key, node.value)
+    }
+    /// Remove the node at `idx` from storage, returning it whole, links and all.
+    /// Moves the last node into its slot, and re-points all links to it.
+    /// Callers are responsible for `idx` being un-linked from any nodes which remain.
+    pub(crate) fn take_node(&mut self, idx: NodeIndex) -> Node<K, V, B::Meta> {
+        let last = NodeIndex(self.nodes.len() - 1);
+        if last != idx {
+            let (parent, left, right) = (self[last].parent, self[last].left, self[last].right);
+            self.replace_child(parent, last, Some(idx));
+            for child in [left, right].iter().flatten() {
+                self[*child].parent = Some(idx);
             }
         }
+        let node = self.nodes.swap_remove(idx.0);
+        if last != idx {
+            self[idx].index = idx;
+        }
+        node
+    }
+}
+
+///
+/// # Map Entry
+///
+/// View into a single key of a [BstMap], which may be occupied or vacant.
+///
+#[derive(Debug)]
+pub enum Entry<'m, K, V, B: Balance = Unbalanced> {
+    Occupied(OccupiedEntry<'m, K, V, B>),
+    Vacant(VacantEntry<'m, K, V, B>),
+}
+impl<'m, K: Ord, V, B: Balance> Entry<'m, K, V, B> {
+    /// Get the entry's key
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(e) => e.key(),
+            Entry::Vacant(e) => &e.key,
+        }
+    }
+    /// Insert `default` if vacant. Returns a mutable reference to the value.
+    pub fn or_insert(self, default: V) -> &'m mut V {
+        self.or_insert_with(|| default)
+    }
+    /// Insert the result of `default` if vacant. Returns a mutable reference to the value.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'m mut V {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(default()),
+        }
+    }
+    /// Insert [Default::default] if vacant. Returns a mutable reference to the value.
+    pub fn or_default(self) -> &'m mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+    /// Apply `f` to the value if occupied
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut e) => {
+                f(e.get_mut());
+                Entry::Occupied(e)
+            }
+            e => e,
+        }
+    }
+}
+
+/// Occupied [Entry]
+#[derive(Debug)]
+pub struct OccupiedEntry<'m, K, V, B: Balance = Unbalanced> {
+    map: &'m mut BstMap<K, V, B>,
+    index: NodeIndex,
+}
+impl<'m, K, V, B: Balance> OccupiedEntry<'m, K, V, B> {
+    /// Get the entry's key
+    pub fn key(&self) -> &K {
+        &self.map[self.index].key
+    }
+    /// Get the entry's node-index
+    pub fn index(&self) -> NodeIndex {
+        self.index
+    }
+    /// Get a reference to the value
+    pub fn get(&self) -> &V {
+        &self.map[self.index].value
+    }
+    /// Get a mutable reference to the value
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map[self.index].value
+    }
+    /// Convert into a mutable reference to the value, with the lifetime of the map
+    pub fn into_mut(self) -> &'m mut V {
+        &mut self.map[self.index].value
+    }
+    /// Replace the value, returning the old one
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+    /// Remove the entry, returning its key and value
+    pub fn remove(self) -> (K, V) {
+        self.map.remove_node(self.index)
+    }
+}
+
+/// Vacant [Entry]
+#[derive(Debug)]
+pub struct VacantEntry<'m, K, V, B: Balance = Unbalanced> {
+    map: &'m mut BstMap<K, V, B>,
+    key: K,
+    /// Parent node and side at which to insert, or `None` for an empty map
+    parent: Option<(NodeIndex, Ordering)>,
+}
+impl<'m, K, V, B: Balance> VacantEntry<'m, K, V, B> {
+    /// Get the entry's key
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+    /// Insert `value`, returning a mutable reference to it
+    pub fn insert(self, value: V) -> &'m mut V {
+        let map = self.map;
+        let idx = map.add_node(self.key, value);
+        map.augment_node(idx);
+        match self.parent {
+            None => map.head = Some(idx),
+            Some((parent, side)) => {
+                map[idx].parent = Some(parent);
+                if side == Ordering::Less {
+                    map[parent].left = Some(idx);
+                } else {
+                    map[parent].right = Some(idx);
+                }
+                map.adjust_sizes(Some(parent), 1);
+            }
+        }
+        // Re-balancing moves links, but not node-storage, so `idx` remains valid
+        B::inserted(map, idx);
+        &mut map[idx].value
+    }
+}
+
+///
+/// # Map Iterator
+///
+/// In-order iterator over a [BstMap], walking between nodes by their `parent` links.
+/// Created by [BstMap::iter] and [BstMap::range].
+/// Double-ended: the front and back each advance toward the other, and iteration ends when they meet.
+///
+#[derive(Debug)]
+pub struct Iter<'t, K, V, B: Balance = Unbalanced> {
+    tree: &'t BstMap<K, V, B>,
+    front: Option<NodeIndex>,
+    back: Option<NodeIndex>,
+}
+impl<'t, K, V, B: Balance> Clone for Iter<'t, K, V, B> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree,
+            front: self.front,
+            back: self.back,
+        }
+    }
+}
+impl<'t, K, V, B: Balance> Iterator for Iter<'t, K, V, B> {
+    type Item = (&'t K, &'t V);
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.front?;
+        if self.front == self.back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.front = self.tree.successor(idx);
+        }
+        let node = &self.tree[idx];
+        Some((&node.key, &node.value))
+    }
+}
+impl<'t, K, V, B: Balance> DoubleEndedIterator for Iter<'t, K, V, B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let idx = self.back?;
+        if self.front == self.back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.back = self.tree.predecessor(idx);
+        }
+        let node = &self.tree[idx];
+        Some((&node.key, &node.value))
+    }
+}
+impl<'t, K, V, B: Balance> IntoIterator for &'t BstMap<K, V, B> {
+    type Item = (&'t K, &'t V);
+    type IntoIter = Iter<'t, K, V, B>;
+    fn into_iter(self) -> Iter<'t, K, V, B> {
+        self.iter()
+    }
+}
+
+/// Iterator over the keys of a [BstMap]
+#[derive(Debug, Clone)]
+pub struct Keys<'t, K, V, B: Balance = Unbalanced>(Iter<'t, K, V, B>);
+impl<'t, K, V, B: Balance> Iterator for Keys<'t, K, V, B> {
+    type Item = &'t K;
+    fn next(&mut self) -> Option<&'t K> {
+        self.0.next().map(|(k, _)| k)
+    }
+}
+impl<'t, K, V, B: Balance> DoubleEndedIterator for Keys<'t, K, V, B> {
+    fn next_back(&mut self) -> Option<&'t K> {
+        self.0.next_back().map(|(k, _)| k)
+    }
+}
+
+/// Iterator over the values of a [BstMap]
+#[derive(Debug, Clone)]
+pub struct Values<'t, K, V, B: Balance = Unbalanced>(Iter<'t, K, V, B>);
+impl<'t, K, V, B: Balance> Iterator for Values<'t, K, V, B> {
+    type Item = &'t V;
+    fn next(&mut self) -> Option<&'t V> {
+        self.0.next().map(|(_, v)| v)
+    }
+}
+impl<'t, K, V, B: Balance> DoubleEndedIterator for Values<'t, K, V, B> {
+    fn next_back(&mut self) -> Option<&'t V> {
+        self.0.next_back().map(|(_, v)| v)
+    }
+}
+
+///
+/// # Binary Search Tree
+///
+/// Set of `isize` values, as a thin wrapper over a [BstMap] with unit values.
+/// Dereferences to the underlying map for node-level access, e.g. `tree[idx].key` and `tree.head`.
+///
+#[derive(Debug)]
+pub struct BinarySearchTree<B: Balance = Unbalanced> {
+    map: BstMap<isize, (), B>,
+}
+impl Default for BinarySearchTree {
+    fn default() -> Self {
+        Self {
+            map: BstMap::default(),
+        }
+    }
+}
+impl<B: Balance> Deref for BinarySearchTree<B> {
+    type Target = BstMap<isize, (), B>;
+    fn deref(&self) -> &BstMap<isize, (), B> {
+        &self.map
+    }
+}
+impl BinarySearchTree<Avl> {
+    /// Create a new, empty, [Avl]-balanced tree
+    pub fn new_avl() -> Self {
+        Self {
+            map: BstMap::new_avl(),
+        }
+    }
+}
+impl BinarySearchTree<RedBlack> {
+    /// Create a new, empty, [RedBlack]-balanced tree
+    pub fn new_red_black() -> Self {
+        Self {
+            map: BstMap::new_red_black(),
+        }
+    }
+}
+impl<B: Balance> BinarySearchTree<B> {
+    /// Insert value `val`, as a new leaf at the end of its search path.
+    /// Values already present are not duplicated.
+    pub fn insert(&mut self, val: isize) {
+        self.map.insert(val, ());
+    }
+    /// In-order traversal, creating and returning a vector of data-elements
+    pub fn inorder(&self) -> Vec<isize> {
+        self.iter().copied().collect()
+    }
+    /// Iterate over all values, in ascending order
+    pub fn iter(&self) -> Keys<'_, isize, (), B> {
+        self.map.keys()
+    }
+    /// Iterate over values in `range`, in ascending order
+    pub fn range<R: RangeBounds<isize>>(&self, range: R) -> Keys<'_, isize, (), B> {
+        Keys(self.map.range(range))
     }
     /// Find the node with value `val`, if present
     pub fn find(&self, val: isize) -> Option<NodeIndex> {
-        let mut node = self.head;
+        self.map.find(&val)
+    }
+    /// Remove value `val`, if present.
+    /// Returns a boolean indication of whether it was found.
+    pub fn remove(&mut self, val: isize) -> bool {
+        self.map.remove(&val).is_some()
+    }
+    /// Remove node `idx` from the tree, returning its value.
+    /// See [BstMap::remove_node] for the effects on other indices.
+    pub fn remove_node(&mut self, idx: NodeIndex) -> isize {
+        self.map.remove_node(idx).0
+    }
+    /// Get the `k`th smallest value, counting from zero, if in bounds
+    pub fn kth_smallest(&self, k: usize) -> Option<isize> {
+        self.map.select(k).map(|idx| self[idx].key)
+    }
+    /// Get the median value, averaging the two middle values if the tree's size is even.
+    /// Returns `None` if empty.
+    pub fn median(&self) -> Option<f64> {
+        let low = self[self.median_low()?].key as f64;
+        let high = self[self.median_high()?].key as f64;
+        Some((low + high) / 2.0)
+    }
+    /// Serialize the tree's shape to level-order text, as for [TreeView::serialize]
+    pub fn serialize(&self) -> String {
+        self.map.serialize()
+    }
+    /// Parse level-order text, as produced by [BinarySearchTree::serialize].
+    /// Values are inserted in level-order, which reproduces the shape of [Unbalanced] trees exactly.
+    /// Self-balancing trees re-balance as they go, and may settle into a different shape.
+    /// Returns an `Err` if the text fails to parse, or does not describe a valid binary search tree.
+    pub fn deserialize(src: &str) -> Result<Self, binary_tree::Error> {
+        let parsed = BinaryTree::deserialize(src)?;
+        if !validate_bst(&parsed) {
+            return Err(binary_tree::Error);
+        }
+        let mut tree = Self {
+            map: BstMap::empty(),
+        };
+        for ptr in parsed.iter_level_order() {
+            tree.insert(*parsed.data(&ptr));
+        }
+        Ok(tree)
+    }
+}
+/// ASCII-art rendering of the tree's shape, as for [TreeView::render]
+impl<B: Balance> fmt::Display for BinarySearchTree<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.map.render())
+    }
+}
+
+///
+/// # Balance Strategy
+///
+/// Hooks by which a [BstMap] maintains its balance.
+/// Each is called after the tree's links have been updated for an insertion or removal,
+/// and may re-arrange them via rotations, but never moves nodes in storage.
+///
+pub trait Balance: Sized {
+    /// Per-node data, stored in [Node::meta]
+    type Meta: Copy + Debug;
+    /// Get the [Balance::Meta] for a newly-inserted leaf
+    fn leaf() -> Self::Meta;
+    /// Restore balance after linking new leaf `idx` into the tree
+    fn inserted<K, V>(tree: &mut BstMap<K, V, Self>, idx: NodeIndex);
+    /// Notification that a search through mutable access ended at node `idx`, without inserting
+    /// or removing: [BstMap::entry] finding its key, [BstMap::get_mut] either way,
+    /// or [BstMap::remove] missing its key. Defaults to doing nothing.
+    fn accessed<K, V>(_tree: &mut BstMap<K, V, Self>, _idx: NodeIndex) {}
+    /// Restore balance after un-linking a node with [Balance::Meta] `meta`,
+    /// which had parent `parent`, and has been replaced by its only child `child`, if any.
+    fn removed<K, V>(
+        tree: &mut BstMap<K, V, Self>,
+        parent: Option<NodeIndex>,
+        child: Option<NodeIndex>,
+        meta: Self::Meta,
+    );
+    /// Debug check of the strategy's invariants
+    fn check<K: Ord + Clone, V>(tree: &BstMap<K, V, Self>) -> bool;
+}
+
+///
+/// # Unbalanced
+///
+/// Default [Balance] strategy, which does nothing.
+/// Sorted insertions degenerate into a linked list.
+///
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Unbalanced;
+impl Balance for Unbalanced {
+    type Meta = ();
+    fn leaf() {}
+    fn inserted<K, V>(_tree: &mut BstMap<K, V, Self>, _idx: NodeIndex) {}
+    fn removed<K, V>(
+        _tree: &mut BstMap<K, V, Self>,
+        _parent: Option<NodeIndex>,
+        _child: Option<NodeIndex>,
+        _meta: (),
+    ) {
+    }
+    fn check<K: Ord + Clone, V>(_tree: &BstMap<K, V, Self>) -> bool {
+        true
+    }
+}
+
+///
+/// # AVL Balance
+///
+/// Each node stores the height of its sub-tree.
+/// After each insertion or removal, heights are updated from the affected node up to the head,
+/// rotating wherever a node's two sub-tree heights differ by more than one.
+///
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Avl;
+impl Avl {
+    /// Get the height of optional sub-tree `opt`
+    fn height<K, V>(tree: &BstMap<K, V, Self>, opt: Option<NodeIndex>) -> usize {
+        opt.map_or(0, |idx| tree[idx].meta)
+    }
+    /// Recompute the height of `idx` from those of its children
+    fn update<K, V>(tree: &mut BstMap<K, V, Self>, idx: NodeIndex) {
+        let left = Self::height(tree, tree[idx].left);
+        let right = Self::height(tree, tree[idx].right);
+        tree[idx].meta = 1 + left.max(right);
+    }
+    /// Get the left-minus-right height difference at `idx`
+    fn skew<K, V>(tree: &BstMap<K, V, Self>, idx: NodeIndex) -> isize {
+        Self::height(tree, tree[idx].left) as isize - Self::height(tree, tree[idx].right) as isize
+    }
+    /// Re-balance the sub-tree at `idx`, whose children are balanced. Returns its new root.
+    fn rebalance<K, V>(tree: &mut BstMap<K, V, Self>, idx: NodeIndex) -> NodeIndex {
+        Self::update(tree, idx);
+        let skew = Self::skew(tree, idx);
+        if skew > 1 {
+            // Left-heavy. If the left child leans right, first rotate it left.
+            let left = tree[idx].left.unwrap();
+            if Self::skew(tree, left) < 0 {
+                let l = tree.rotate_left(left);
+                Self::update(tree, left);
+                Self::update(tree, l);
+            }
+            let root = tree.rotate_right(idx);
+            Self::update(tree, idx);
+            Self::update(tree, root);
+            root
+        } else if skew < -1 {
+            // Mirror image
+            let right = tree[idx].right.unwrap();
+            if Self::skew(tree, right) > 0 {
+                let r = tree.rotate_right(right);
+                Self::update(tree, right);
+                Self::update(tree, r);
+            }
+            let root = tree.rotate_left(idx);
+            Self::update(tree, idx);
+            Self::update(tree, root);
+            root
+        } else {
+            idx
+        }
+    }
+    /// Re-balance each node from `start` up to the head
+    fn retrace<K, V>(tree: &mut BstMap<K, V, Self>, start: Option<NodeIndex>) {
+        let mut node = start;
         while let Some(idx) = node {
-            if self[idx].data == val {
-                return Some(idx);
+            let root = Self::rebalance(tree, idx);
+            node = tree[root].parent;
+        }
+    }
+}
+impl Balance for Avl {
+    /// Sub-tree height
+    type Meta = usize;
+    fn leaf() -> usize {
+        1
+    }
+    fn inserted<K, V>(tree: &mut BstMap<K, V, Self>, idx: NodeIndex) {
+        let parent = tree[idx].parent;
+        Self::retrace(tree, parent);
+    }
+    fn removed<K, V>(
+        tree: &mut BstMap<K, V, Self>,
+        parent: Option<NodeIndex>,
+        _child: Option<NodeIndex>,
+        _meta: usize,
+    ) {
+        Self::retrace(tree, parent);
+    }
+    fn check<K: Ord + Clone, V>(tree: &BstMap<K, V, Self>) -> bool {
+        let heights_ok = tree.nodes.iter().all(|node| {
+            let expected = 1 + Self::height(tree, node.left).max(Self::height(tree, node.right));
+            node.meta == expected
+        });
+        heights_ok && check_balanced(tree)
+    }
+}
+
+/// Red-Black Node Color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Black,
+}
+
+///
+/// # Red-Black Balance
+///
+/// Each node is colored red or black, such that:
+/// * The head is black,
+/// * No red node has a red child, and
+/// * Every path from a node down to a missing child passes through the same number of black nodes.
+///
+/// Together these keep the longest path no more than twice the shortest.
+/// Insertions and removals re-color and rotate, per Cormen et al.
+///
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RedBlack;
+impl RedBlack {
+    /// Get the color of optional node `opt`. Missing nodes are black.
+    fn color<K, V>(tree: &BstMap<K, V, Self>, opt: Option<NodeIndex>) -> Color {
+        opt.map_or(Color::Black, |idx| tree[idx].meta)
+    }
+    /// Boolean indication of whether optional node `opt` is red
+    fn is_red<K, V>(tree: &BstMap<K, V, Self>, opt: Option<NodeIndex>) -> bool {
+        Self::color(tree, opt) == Color::Red
+    }
+    /// Get the black-height of sub-tree `opt`, or `None` if its paths disagree or red nodes are adjacent
+    fn black_height<K, V>(tree: &BstMap<K, V, Self>, opt: Option<NodeIndex>) -> Option<usize> {
+        let idx = match opt {
+            None => return Some(1),
+            Some(idx) => idx,
+        };
+        let node = &tree[idx];
+        if node.meta == Color::Red
+            && (Self::is_red(tree, node.left) || Self::is_red(tree, node.right))
+        {
+            return None; // Red node with a red child
+        }
+        let left = Self::black_height(tree, node.left)?;
+        let right = Self::black_height(tree, node.right)?;
+        if left != right {
+            return None;
+        }
+        Some(left + (node.meta == Color::Black) as usize)
+    }
+}
+impl Balance for RedBlack {
+    type Meta = Color;
+    fn leaf() -> Color {
+        Color::Red
+    }
+    fn inserted<K, V>(tree: &mut BstMap<K, V, Self>, idx: NodeIndex) {
+        let mut node = idx;
+        loop {
+            // While `node` is red, so is its parent, in violation
+            let parent = match tree[node].parent {
+                Some(p) if tree[p].meta == Color::Red => p,
+                _ => break,
+            };
+            // A red parent is never the head, so always has a parent of its own
+            let grand = tree[parent].parent.unwrap();
+            let parent_is_left = tree[grand].left == Some(parent);
+            let uncle = if parent_is_left {
+                tree[grand].right
+            } else {
+                tree[grand].left
+            };
+            if let Some(u) = uncle.filter(|&u| tree[u].meta == Color::Red) {
+                // Red uncle: push the grandparent's black down a level, and continue from it
+                tree[parent].meta = Color::Black;
+                tree[u].meta = Color::Black;
+                tree[grand].meta = Color::Red;
+                node = grand;
+                continue;
             }
-            node = if self[idx].data > val {
-                self[idx].left
+            // Black uncle: rotate `node` to the outside if necessary, then rotate the grandparent
+            let mut parent = parent;
+            if parent_is_left {
+                if tree[parent].right == Some(node) {
+                    tree.rotate_left(parent);
+                    parent = node;
+                }
+                tree.rotate_right(grand);
             } else {
-                self[idx].right
+                if tree[parent].left == Some(node) {
+                    tree.rotate_right(parent);
+                    parent = node;
+                }
+                tree.rotate_left(grand);
+            }
+            tree[parent].meta = Color::Black;
+            tree[grand].meta = Color::Red;
+            break;
+        }
+        let head = tree.head.unwrap();
+        tree[head].meta = Color::Black;
+    }
+    fn removed<K, V>(
+        tree: &mut BstMap<K, V, Self>,
+        parent: Option<NodeIndex>,
+        child: Option<NodeIndex>,
+        meta: Color,
+    ) {
+        if meta == Color::Red {
+            return; // Removing a red node changes no black-heights
+        }
+        // Otherwise the path through `node` is one black short.
+        // Push the deficit upward until it can be absorbed by a red node, or reaches the head.
+        let (mut node, mut parent) = (child, parent);
+        while node != tree.head && !Self::is_red(tree, node) {
+            // Not the head, so has a parent.
+            // And a (non-empty) sibling, whose paths are one black longer than our own.
+            let p = parent.unwrap();
+            let node_is_left = tree[p].left == node;
+            let sibling_of = |tree: &BstMap<K, V, Self>| {
+                if node_is_left {
+                    tree[p].right.unwrap()
+                } else {
+                    tree[p].left.unwrap()
+                }
             };
+            let mut sibling = sibling_of(tree);
+            if tree[sibling].meta == Color::Red {
+                // Red sibling: rotate it up, to get a black one
+                tree[sibling].meta = Color::Black;
+                tree[p].meta = Color::Red;
+                if node_is_left {
+                    tree.rotate_left(p);
+                } else {
+                    tree.rotate_right(p);
+                }
+                sibling = sibling_of(tree);
+            }
+            let (near, far) = if node_is_left {
+                (tree[sibling].left, tree[sibling].right)
+            } else {
+                (tree[sibling].right, tree[sibling].left)
+            };
+            if !Self::is_red(tree, near) && !Self::is_red(tree, far) {
+                // Black sibling with black children: re-color it, and move the deficit up
+                tree[sibling].meta = Color::Red;
+                node = Some(p);
+                parent = tree[p].parent;
+                continue;
+            }
+            if !Self::is_red(tree, far) {
+                // Red near nephew: rotate it into the sibling's place
+                tree[near.unwrap()].meta = Color::Black;
+                tree[sibling].meta = Color::Red;
+                if node_is_left {
+                    tree.rotate_right(sibling);
+                } else {
+                    tree.rotate_left(sibling);
+                }
+                sibling = sibling_of(tree);
+            }
+            // Red far nephew: rotate the parent toward `node`, absorbing the deficit. Done.
+            let far = if node_is_left {
+                tree[sibling].right
+            } else {
+                tree[sibling].left
+            };
+            tree[sibling].meta = tree[p].meta;
+            tree[p].meta = Color::Black;
+            tree[far.unwrap()].meta = Color::Black;
+            if node_is_left {
+                tree.rotate_left(p);
+            } else {
+                tree.rotate_right(p);
+            }
+            node = tree.head;
+            parent = None;
+        }
+        if let Some(n) = node {
+            tree[n].meta = Color::Black;
         }
-        None // Not found
     }
+    fn check<K: Ord + Clone, V>(tree: &BstMap<K, V, Self>) -> bool {
+        Self::color(tree, tree.head) == Color::Black
+            && Self::black_height(tree, tree.head).is_some()
+    }
+}
+
+#[test]
+fn test_min_max_successor() {
+    let mut bst = BinarySearchTree::default();
+    assert_eq!(bst.min(), None);
+    for k in [20, 9, 25, 5, 12, 11, 14, 30] {
+        bst.insert(k);
+    }
+    assert_eq!(bst[bst.min().unwrap()].key, 5);
+    assert_eq!(bst[bst.max().unwrap()].key, 30);
+
+    // Walk forward by successors, and back by predecessors
+    let mut forward = Vec::new();
+    let mut node = bst.min();
+    while let Some(idx) = node {
+        forward.push(bst[idx].key);
+        node = bst.successor(idx);
+    }
+    assert_eq!(forward, bst.inorder());
+
+    let mut backward = Vec::new();
+    let mut node = bst.max();
+    while let Some(idx) = node {
+        backward.push(bst[idx].key);
+        node = bst.predecessor(idx);
+    }
+    backward.reverse();
+    assert_eq!(backward, forward);
+}
+
+#[test]
+fn test_remove() {
+    let mut bst = BinarySearchTree::default();
+    let values = [20, 9, 25, 5, 12, 11, 14, 30, 22, 27];
+    for &k in values.iter() {
+        bst.insert(k);
+    }
+    assert!(bst.check_invariants());
+    assert!(!bst.remove(100));
+
+    // Leaf, single-child, two-child, and head removals, checking links after each
+    let mut remaining: Vec<isize> = values.to_vec();
+    for &k in [5, 30, 9, 20, 25, 11, 27, 22, 14, 12].iter() {
+        assert!(bst.remove(k));
+        remaining.retain(|&r| r != k);
+        remaining.sort();
+        assert!(bst.check_invariants());
+        assert_eq!(bst.inorder(), remaining);
+        assert_eq!(bst.nodes.len(), remaining.len());
+    }
+    assert!(bst.is_empty());
+    assert!(bst.head.is_none());
+
+    // The tree remains usable after emptying
+    bst.insert(3);
+    bst.insert(1);
+    bst.insert(3); // Not duplicated
+    assert!(bst.check_invariants());
+    assert_eq!(bst.remove_node(bst.head.unwrap()), 3);
+    assert_eq!(bst.inorder(), &[1]);
+    assert!(bst.check_invariants());
+}
+
+#[test]
+fn test_insert_placement() {
+    // New values become leaves, rather than being spliced in above existing nodes
+    let mut bst = BinarySearchTree::default();
+    for k in [20, 9, 25, 5, 12] {
+        bst.insert(k);
+    }
+    assert_eq!(bst.serialize(), "[20,9,25,5,12]");
+
+    let mut bst = BinarySearchTree::default();
+    for k in [20, 9, 19, 15] {
+        bst.insert(k);
+    }
+    assert_eq!(bst.serialize(), "[20,9,null,null,19,15]");
+    assert!(bst.check_invariants());
+
+    // And duplicates are ignored
+    bst.insert(19);
+    assert_eq!(bst.len(), 4);
+    assert_eq!(bst.inorder(), &[9, 15, 19, 20]);
+}
+
+#[test]
+fn test_bst_map() {
+    let mut map = BstMap::new();
+    assert_eq!(map.insert("m".to_string(), 1), None);
+    assert_eq!(map.insert("c".to_string(), 2), None);
+    assert_eq!(map.insert("x".to_string(), 3), None);
+    assert_eq!(map.insert("c".to_string(), 4), Some(2));
+    assert_eq!(map.len(), 3);
+
+    // Lookups by borrowed key
+    assert_eq!(map.get("c"), Some(&4));
+    assert_eq!(map.get("q"), None);
+    *map.get_mut("x").unwrap() += 10;
+    assert_eq!(map.get("x"), Some(&13));
+    assert!(map.contains_key("m"));
+
+    assert_eq!(map.remove("m"), Some(1));
+    assert_eq!(map.remove("m"), None);
+    assert_eq!(map.len(), 2);
+    assert!(map.check_invariants());
+}
+
+#[test]
+fn test_bst_map_entry() {
+    // Word counts, via the entry API
+    let mut counts: BstMap<&str, usize> = BstMap::new();
+    for word in "the quick fox and the lazy dog and the cat".split(' ') {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    assert_eq!(counts.get("the"), Some(&3));
+    assert_eq!(counts.get("and"), Some(&2));
+    assert_eq!(counts.len(), 7);
+
+    counts.entry("fox").and_modify(|c| *c += 10).or_default();
+    counts.entry("owl").and_modify(|c| *c += 10).or_default();
+    assert_eq!(counts.get("fox"), Some(&11));
+    assert_eq!(counts.get("owl"), Some(&0));
+
+    match counts.entry("dog") {
+        Entry::Occupied(e) => assert_eq!(e.remove(), ("dog", 1)),
+        Entry::Vacant(_) => panic!("dog should be present"),
+    }
+    assert!(!counts.contains_key("dog"));
+    assert!(counts.check_invariants());
+
+    // Values need be neither `Clone` nor `Default`
+    struct Record(String);
+    let mut records = BstMap::new();
+    records.insert(2, Record("two".into()));
+    records.entry(1).or_insert_with(|| Record("one".into()));
+    assert_eq!(records.get(&1).map(|r| r.0.as_str()), Some("one"));
+}
+
+#[test]
+fn test_balanced_sorted_inserts() {
+    // Sorted inserts, which degenerate an unbalanced tree into a list
+    let mut plain = BinarySearchTree::default();
+    let mut avl = BinarySearchTree::new_avl();
+    let mut rb = BinarySearchTree::new_red_black();
+    for k in 0..127 {
+        plain.insert(k);
+        avl.insert(k);
+        rb.insert(k);
+    }
+    assert!(!check_balanced(&*plain));
+    assert!(plain.check_invariants());
+    assert!(avl.check_invariants());
+    assert!(rb.check_invariants());
+
+    // A perfect AVL tree of 2^7 - 1 nodes has height 7
+    assert_eq!(avl[avl.head.unwrap()].meta, 7);
+    assert_eq!(avl.inorder(), (0..127).collect::<Vec<_>>());
+    assert_eq!(rb.inorder(), (0..127).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_balanced_random() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(39);
+    let mut avl = AvlMap::new_avl();
+    let mut rb = RedBlackMap::new_red_black();
+    let mut reference = std::collections::BTreeMap::new();
+    for step in 0..2_000 {
+        let key: i32 = rng.gen_range(0..200);
+        if rng.gen_bool(0.6) {
+            let expected = reference.insert(key, step);
+            assert_eq!(avl.insert(key, step), expected);
+            assert_eq!(rb.insert(key, step), expected);
+        } else {
+            let expected = reference.remove(&key);
+            assert_eq!(avl.remove(&key), expected);
+            assert_eq!(rb.remove(&key), expected);
+        }
+        if step % 50 == 0 {
+            assert!(avl.check_invariants());
+            assert!(rb.check_invariants());
+        }
+        assert_eq!(avl.len(), reference.len());
+        assert_eq!(rb.len(), reference.len());
+    }
+    assert!(avl.check_invariants());
+    assert!(rb.check_invariants());
+    for (key, value) in reference.iter() {
+        assert_eq!(avl.get(key), Some(value));
+        assert_eq!(rb.get(key), Some(value));
+    }
+
+    // Entries re-balance on insert too
+    let mut rb = RedBlackMap::new_red_black();
+    for k in 0..100 {
+        *rb.entry(k).or_insert(0) += 1;
+    }
+    assert!(rb.check_invariants());
+}
+
+#[test]
+fn test_iter() {
+    let mut bst = BinarySearchTree::new_avl();
+    assert_eq!(bst.iter().next(), None);
+    for k in [20, 9, 25, 5, 12, 11, 14, 30] {
+        bst.insert(k);
+    }
+    let sorted = vec![5, 9, 11, 12, 14, 20, 25, 30];
+    assert_eq!(bst.iter().copied().collect::<Vec<_>>(), sorted);
+    assert_eq!(
+        bst.iter().rev().copied().collect::<Vec<_>>(),
+        sorted.iter().rev().copied().collect::<Vec<_>>()
+    );
+
+    // Alternating from both ends meets in the middle, yielding each exactly once
+    let mut iter = bst.iter();
+    let mut seen = Vec::new();
+    loop {
+        match (iter.next(), iter.next_back()) {
+            (Some(a), Some(b)) => seen.extend([*a, *b]),
+            (Some(a), None) => seen.push(*a),
+            _ => break,
+        }
+    }
+    seen.sort();
+    assert_eq!(seen, sorted);
+
+    // Map iteration, keys, and values
+    let mut map = BstMap::new();
+    for (i, word) in ["pear", "apple", "fig"].iter().enumerate() {
+        map.insert(*word, i);
+    }
+    let entries: Vec<_> = map.iter().collect();
+    assert_eq!(entries, vec![(&"apple", &1), (&"fig", &2), (&"pear", &0)]);
+    assert_eq!(
+        map.keys().rev().collect::<Vec<_>>(),
+        vec![&"pear", &"fig", &"apple"]
+    );
+    assert_eq!(map.values().sum::<usize>(), 3);
+    assert_eq!((&map).into_iter().count(), 3);
+}
+
+#[test]
+fn test_range() {
+    let mut bst = BinarySearchTree::new_red_black();
+    for k in (0..100).step_by(10) {
+        bst.insert(k);
+    }
+    let range = |r: (Bound<isize>, Bound<isize>)| bst.range(r).copied().collect::<Vec<_>>();
+    assert_eq!(
+        bst.range(20..50).copied().collect::<Vec<_>>(),
+        &[20, 30, 40]
+    );
+    assert_eq!(
+        bst.range(15..=50).copied().collect::<Vec<_>>(),
+        &[20, 30, 40, 50]
+    );
+    assert_eq!(bst.range(..=15).copied().collect::<Vec<_>>(), &[0, 10]);
+    assert_eq!(bst.range(85..).copied().collect::<Vec<_>>(), &[90]);
+    assert_eq!(bst.range(..).count(), 10);
+    assert_eq!(range((Bound::Excluded(20), Bound::Excluded(50))), &[30, 40]);
+    assert_eq!(
+        bst.range(20..60).rev().copied().collect::<Vec<_>>(),
+        &[50, 40, 30, 20]
+    );
+
+    // Empty ranges
+    assert_eq!(bst.range(41..49).next(), None);
+    assert_eq!(bst.range(200..).next(), None);
+    assert_eq!(bst.range(..0).next(), None);
+    assert_eq!(
+        range((Bound::Excluded(30), Bound::Excluded(30))),
+        Vec::<isize>::new()
+    );
+
+    // Maps support ranges over borrowed keys
+    let mut map = BstMap::new();
+    for word in ["ant", "bee", "cat", "dog", "eel"] {
+        map.insert(word.to_string(), word.len());
+    }
+    let keys: Vec<_> = map
+        .range::<str, _>((Bound::Included("b"), Bound::Excluded("d")))
+        .map(|(k, _)| k.as_str())
+        .collect();
+    assert_eq!(keys, vec!["bee", "cat"]);
+}
+
+#[test]
+fn test_order_statistics() {
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    let mut values: Vec<isize> = (0..200).map(|k| k * 3).collect();
+    values.shuffle(&mut StdRng::seed_from_u64(41));
+
+    let mut plain = BinarySearchTree::default();
+    let mut avl = BinarySearchTree::new_avl();
+    let mut rb = BinarySearchTree::new_red_black();
+    for &v in values.iter() {
+        plain.insert(v);
+        avl.insert(v);
+        rb.insert(v);
+    }
+    // Remove every fourth, exercising size-maintenance through removals and rotations
+    for &v in values.iter().step_by(4) {
+        plain.remove(v);
+        avl.remove(v);
+        rb.remove(v);
+    }
+    let removed: Vec<isize> = values.iter().copied().step_by(4).collect();
+    let mut sorted: Vec<isize> = values
+        .iter()
+        .copied()
+        .filter(|v| !removed.contains(v))
+        .collect();
+    sorted.sort();
+
+    assert!(plain.check_invariants());
+    assert!(avl.check_invariants());
+    assert!(rb.check_invariants());
+    for (k, &v) in sorted.iter().enumerate() {
+        assert_eq!(plain.rank(&v), k);
+        assert_eq!(avl.rank(&v), k);
+        assert_eq!(rb.rank(&v), k);
+        assert_eq!(plain.kth_smallest(k), Some(v));
+        assert_eq!(avl.kth_smallest(k), Some(v));
+        assert_eq!(rb.kth_smallest(k), Some(v));
+        // Absent keys rank between their neighbors
+        assert_eq!(avl.rank(&(v + 1)), k + 1);
+    }
+    assert_eq!(avl.kth_smallest(sorted.len()), None);
+
+    let count = |lo: isize, hi: isize| sorted.iter().filter(|&&v| lo <= v && v < hi).count();
+    assert_eq!(rb.count_range(100..200), count(100, 200));
+    assert_eq!(rb.count_range(..), sorted.len());
+    assert_eq!(rb.count_range(..=99), count(isize::MIN, 100));
+    assert_eq!(rb.count_range(300..), count(300, isize::MAX));
+    assert_eq!(
+        rb.count_range((Bound::Included(50), Bound::Excluded(10))),
+        0
+    );
+}
+
+#[test]
+fn test_median() {
+    let mut bst = BinarySearchTree::new_avl();
+    assert_eq!(bst.median(), None);
+    for k in [5, 1, 9, 3] {
+        bst.insert(k);
+    }
+    assert_eq!(bst[bst.median_low().unwrap()].key, 3);
+    assert_eq!(bst[bst.median_high().unwrap()].key, 5);
+    assert_eq!(bst.median(), Some(4.0));
+    bst.insert(7);
+    assert_eq!(bst.median(), Some(5.0));
+}
+
+#[test]
+fn test_serialize() {
+    let mut tree = BinarySearchTree::default();
+    for val in [5, 3, 8, 1, 4, 9, 2] {
+        tree.insert(val);
+    }
+    let src = tree.serialize();
+    assert_eq!(src, "[5,3,8,1,4,null,9,null,2]");
+    let parsed = BinarySearchTree::<Unbalanced>::deserialize(&src).unwrap();
+    assert_eq!(parsed.serialize(), src);
+    assert_eq!(
+        parsed.to_string(),
+        [
+            "   _5",
+            "  /  \\",
+            " _3  8",
+            "/  \\  \\",
+            "1  4  9",
+            " \\",
+            " 2"
+        ]
+        .join("\n")
+    );
+
+    // Balanced trees keep the contents, but may re-shape
+    let avl = BinarySearchTree::<Avl>::deserialize("[1,null,2,null,3]").unwrap();
+    assert_eq!(avl.serialize(), "[2,1,3]");
+    assert!(avl.check_invariants());
+
+    // Out-of-order values are rejected
+    assert!(BinarySearchTree::<Unbalanced>::deserialize("[5,6,4]").is_err());
+    assert!(BinarySearchTree::<Unbalanced>::deserialize("[5,3]x").is_err());
 }
@@ -3,9 +3,24 @@
 //!
 //! Connections use the crate-level [Ptr] pointer-type, a wrapper over `Rc<RefCell>`.
 //!
+//! Traversals and other read-only operations are provided by the [TreeView] trait.
+//! Its iterators yield [NodePtr]s, cheap reference-counted handles to each node, rather than `&T`:
+//! references into a `RefCell` cannot outlive the borrow which produced them.
+//! Node data is accessed via `ptr.borrow().data` or [TreeView::data], and is never cloned.
+//!
+//! Trees convert to and from a level-order text format, e.g. `[1,2,3,null,4]`,
+//! in which `null` marks each absent child, and trailing `null`s are dropped.
+//! Their [Display](fmt::Display) implementation draws an ASCII-art rendering of their shape.
+//!
+
+use std::cell::Ref;
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
 
 // Local Imports
 use crate::ptr::Ptr;
+use crate::tree_view::TreeView;
 
 // Alias for a [Ptr] to a [Node]
 pub type NodePtr<T> = Ptr<Node<T>>;
@@ -62,24 +77,190 @@ impl<T: Clone> BinaryTree<T> {
     }
     /// In-order traversal, creating and returning a vector of data-elements
     pub fn inorder(&self) -> Vec<T> {
-        let mut v = Vec::new();
-        self._inorder(&self.head, &mut v);
-        v
+        self.iter_inorder()
+            .map(|ptr| ptr.borrow().data.clone())
+            .collect()
+    }
+}
+impl<T> TreeView for BinaryTree<T> {
+    type Data = T;
+    type Node = NodePtr<T>;
+    type DataRef<'a>
+        = Ref<'a, T>
+    where
+        T: 'a;
+    fn root(&self) -> Option<NodePtr<T>> {
+        self.head.clone()
+    }
+    fn left(&self, node: &NodePtr<T>) -> Option<NodePtr<T>> {
+        node.borrow().left.clone()
+    }
+    fn right(&self, node: &NodePtr<T>) -> Option<NodePtr<T>> {
+        node.borrow().right.clone()
+    }
+    fn data<'a>(&'a self, node: &'a NodePtr<T>) -> Ref<'a, T> {
+        Ref::map(node.borrow(), |n| &n.data)
     }
-    /// Recursive helper for `inorder`
-    fn _inorder(&self, opt: &Option<NodePtr<T>>, v: &mut Vec<T>) {
-        match opt {
-            None => (),
-            Some(ptr) => {
-                let node = &ptr.borrow();
-                self._inorder(&node.left, v);
-                v.push(node.data.clone());
-                self._inorder(&node.right, v);
+}
+
+impl<T: FromStr> BinaryTree<T> {
+    /// Parse level-order text, as produced by [TreeView::serialize].
+    /// Round-trips for data whose text contains no commas or brackets, and is not itself `null`.
+    /// Whitespace around each element is ignored.
+    /// Returns an `Err` if any element fails to parse, or if elements remain after every node's
+    /// children have been assigned.
+    pub fn deserialize(src: &str) -> Result<Self, Error> {
+        let mut tokens = level_order_tokens(src)?.into_iter();
+        if tokens.len() == 0 {
+            return Ok(Self::default());
+        }
+        let parse = |token: &str| -> Result<Option<NodePtr<T>>, Error> {
+            match token {
+                "null" => Ok(None),
+                _ => {
+                    let data = token.parse().map_err(|_| Error)?;
+                    Ok(Some(NodePtr::new(Node::new(data, None, None))))
+                }
             }
+        };
+        let head = parse(tokens.next().ok_or(Error)?)?;
+        // Queue of nodes awaiting children, in level-order
+        let mut queue: VecDeque<NodePtr<T>> = head.iter().cloned().collect();
+        while let Some(ptr) = queue.pop_front() {
+            let mut node = ptr.borrow_mut();
+            node.left = tokens.next().map(parse).transpose()?.flatten();
+            node.right = tokens.next().map(parse).transpose()?.flatten();
+            queue.extend(node.left.iter().cloned());
+            queue.extend(node.right.iter().cloned());
         }
+        if tokens.next().is_some() {
+            return Err(Error); // Elements without a parent
+        }
+        Ok(Self { head })
+    }
+}
+
+/// Split level-order text into its trimmed elements, e.g. `"[1, null,2]"` into `["1", "null", "2"]`.
+/// An empty list, `"[]"`, has no elements. Returns an `Err` if the brackets are missing.
+pub(crate) fn level_order_tokens(src: &str) -> Result<Vec<&str>, Error> {
+    let inner = src
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or(Error)?;
+    if inner.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(inner.split(',').map(str::trim).collect())
+}
+
+/// ASCII-art rendering, as for [TreeView::render]
+impl<T: fmt::Display> fmt::Display for BinaryTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
     }
 }
 
 /// Local Error Type
 #[derive(Debug, PartialEq, Eq)]
 pub struct Error;
+
+#[test]
+fn test_traversals() {
+    //       1
+    //     /   \
+    //    2     3
+    //   / \     \
+    //  4   5     6
+    //     /
+    //    7
+    let leaf = |data| Some(NodePtr::new(Node::new(data, None, None)));
+    let five = Some(NodePtr::new(Node::new(5, leaf(7), None)));
+    let two = Some(NodePtr::new(Node::new(2, leaf(4), five)));
+    let three = Some(NodePtr::new(Node::new(3, None, leaf(6))));
+    let tree = BinaryTree {
+        head: Some(NodePtr::new(Node::new(1, two, three))),
+    };
+    let data = |iter: &mut dyn Iterator<Item = NodePtr<i32>>| -> Vec<i32> {
+        iter.map(|ptr| ptr.borrow().data).collect()
+    };
+    assert_eq!(data(&mut tree.iter_preorder()), [1, 2, 4, 5, 7, 3, 6]);
+    assert_eq!(data(&mut tree.iter_inorder()), [4, 2, 7, 5, 1, 3, 6]);
+    assert_eq!(data(&mut tree.iter_postorder()), [4, 7, 5, 2, 6, 3, 1]);
+    assert_eq!(data(&mut tree.iter_level_order()), [1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(data(&mut tree.leaves()), [4, 7, 6]);
+    assert_eq!(tree.inorder(), [4, 2, 7, 5, 1, 3, 6]);
+
+    let levels: Vec<Vec<i32>> = tree
+        .levels()
+        .map(|level| data(&mut level.into_iter()))
+        .collect();
+    assert_eq!(levels, [vec![1], vec![2, 3], vec![4, 5, 6], vec![7]]);
+    assert_eq!(tree.len(), 7);
+    assert_eq!(tree.depth(), 4);
+
+    let empty: BinaryTree<i32> = BinaryTree::default();
+    assert!(empty.is_empty());
+    assert_eq!(empty.len(), 0);
+    assert_eq!(empty.depth(), 0);
+    assert_eq!(empty.iter_postorder().count(), 0);
+}
+
+#[test]
+fn test_serialize() {
+    let src = "[1,2,3,4,5,null,6,null,null,7]";
+    let tree: BinaryTree<i32> = BinaryTree::deserialize(src).unwrap();
+    assert_eq!(tree.serialize(), src);
+    assert_eq!(tree.len(), 7);
+    assert_eq!(tree.inorder(), [4, 2, 7, 5, 1, 3, 6]);
+
+    // Whitespace and trailing `null`s are accepted
+    let tree: BinaryTree<i32> = BinaryTree::deserialize(" [ 1, null, 2, null, null ] ").unwrap();
+    assert_eq!(tree.serialize(), "[1,null,2]");
+
+    let empty: BinaryTree<i32> = BinaryTree::deserialize("[]").unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(empty.serialize(), "[]");
+    assert_eq!(
+        BinaryTree::<i32>::deserialize("[null]")
+            .unwrap()
+            .serialize(),
+        "[]"
+    );
+
+    assert_eq!(BinaryTree::<i32>::deserialize("1,2").unwrap_err(), Error);
+    assert_eq!(BinaryTree::<i32>::deserialize("[1,x]").unwrap_err(), Error);
+    assert_eq!(BinaryTree::<i32>::deserialize("[1,,2]").unwrap_err(), Error);
+    assert_eq!(
+        BinaryTree::<i32>::deserialize("[null,1]").unwrap_err(),
+        Error
+    );
+    assert_eq!(
+        BinaryTree::<i32>::deserialize("[1,null,null,2]").unwrap_err(),
+        Error
+    );
+}
+
+#[test]
+fn test_render() {
+    let render = |src| BinaryTree::<i32>::deserialize(src).unwrap().to_string();
+    assert_eq!(render("[]"), "");
+    assert_eq!(render("[7]"), "7");
+    assert_eq!(
+        render("[1,2,3,4,5]"),
+        ["  _1", " /  \\", " 2  3", "/ \\", "4 5"].join("\n")
+    );
+    assert_eq!(
+        render("[10,2,300,null,4]"),
+        [" _10_", "/    \\", "2   300", " \\", " 4"].join("\n")
+    );
+
+    // Empty labels take up a single (blank) column
+    let mut tree = BinaryTree::default();
+    let root = tree.insert("a".to_string(), None).unwrap();
+    let child = tree.insert(String::new(), Some(root)).unwrap();
+    assert_eq!(tree.to_string(), [" a", "/"].join("\n"));
+    tree.insert("b".to_string(), Some(child.clone())).unwrap();
+    tree.insert(String::new(), Some(child)).unwrap();
+    assert_eq!(tree.to_string(), ["  _a", " /", "", "/ \\", "b"].join("\n"));
+}
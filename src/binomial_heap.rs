@@ -0,0 +1,372 @@
+//!
+//! # Binomial Heap
+//!
+//! Mergeable heap with O(log n) `push`, `pop`, `meld`, and `decrease_key`.
+//!
+//! Stored as a forest of binomial trees, at most one of each order,
+//! indexed by order much like the bits of a binary counter.
+//! Melding two heaps is then akin to binary addition, carrying linked trees up to the next order.
+//!
+
+use std::{
+    cell::{Ref, RefCell},
+    cmp::Ordering,
+    rc::{Rc, Weak},
+};
+
+// Local Imports
+use crate::heap::{Compare, MinOrder, Owner, PriorityQueue};
+
+/// Alias for the shared pointer-type to a [Node]
+type NodePtr<T> = Rc<RefCell<Node<T>>>;
+
+/// Binomial Tree Node
+///
+/// A node of order `k` has exactly `k` children, of orders `0..k`, stored in that order.
+///
+#[derive(Debug)]
+struct Node<T> {
+    data: T,
+    slot: Rc<Slot<T>>,
+    /// Identity of the heap into which the element was pushed
+    owner: Rc<Owner>,
+    parent: Option<Weak<RefCell<Node<T>>>>,
+    children: Vec<NodePtr<T>>,
+}
+
+/// Indirection between [Handle]s and [Node]s.
+///
+/// `decrease_key` moves data between nodes as it sifts upward.
+/// Each element's [Slot] moves along with it, and is updated to point to its new node.
+type Slot<T> = RefCell<Weak<RefCell<Node<T>>>>;
+
+///
+/// # Element Handle
+///
+/// Returned by [BinomialHeap::push], and used to address an element for [BinomialHeap::decrease_key].
+/// Becomes invalid once the element is popped.
+///
+#[derive(Debug)]
+pub struct Handle<T>(Rc<Slot<T>>);
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+///
+/// # Binomial Heap
+///
+/// Ordering is set by comparator-type `C`, which defaults to [MinOrder].
+///
+#[derive(Debug)]
+pub struct BinomialHeap<T, C = MinOrder> {
+    /// Root of each binomial tree, indexed by order
+    roots: Vec<Option<NodePtr<T>>>,
+    len: usize,
+    cmp: C,
+    owner: Rc<Owner>,
+}
+impl<T: Ord> Default for BinomialHeap<T> {
+    fn default() -> Self {
+        Self::with_comparator(MinOrder)
+    }
+}
+impl<T: Ord> BinomialHeap<T> {
+    /// Create a new, empty min-heap. Also available via [Default].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl<T, C: Compare<T>> BinomialHeap<T, C> {
+    /// Create a new, empty heap ordered by comparator `cmp`
+    pub fn with_comparator(cmp: C) -> Self {
+        Self {
+            roots: Vec::new(),
+            len: 0,
+            cmp,
+            owner: Owner::new(),
+        }
+    }
+    /// Push an element onto the heap. Returns a [Handle] to it.
+    pub fn push(&mut self, t: T) -> Handle<T> {
+        let slot = Rc::new(RefCell::new(Weak::new()));
+        let node = Rc::new(RefCell::new(Node {
+            data: t,
+            slot: Rc::clone(&slot),
+            owner: Rc::clone(&self.owner),
+            parent: None,
+            children: Vec::new(),
+        }));
+        *slot.borrow_mut() = Rc::downgrade(&node);
+
+        // Add it as a single order-zero tree
+        self.merge_roots(vec![Some(node)]);
+        self.len += 1;
+        Handle(slot)
+    }
+    /// Pop the top element from the heap. Returns `None` if empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let order = self.top_order()?;
+        let root = self.roots[order].take().unwrap();
+        self.len -= 1;
+
+        // The popped root's children form a binomial heap of their own. Merge them back in.
+        let children: Vec<_> = root
+            .borrow_mut()
+            .children
+            .drain(..)
+            .map(|child| {
+                child.borrow_mut().parent = None;
+                Some(child)
+            })
+            .collect();
+        self.merge_roots(children);
+        self.trim();
+
+        // The old root is now referenced only weakly. Unwrap and return its data.
+        match Rc::try_unwrap(root) {
+            Ok(cell) => Some(cell.into_inner().data),
+            Err(_) => unreachable!("Internal Error: popped node still shared"),
+        }
+    }
+    /// Get a reference to the top element, without removing it. Returns `None` if empty.
+    pub fn peek(&self) -> Option<Ref<'_, T>> {
+        let order = self.top_order()?;
+        let root = self.roots[order].as_ref().unwrap();
+        Some(Ref::map(root.borrow(), |node| &node.data))
+    }
+    /// Get the number of elements in the heap
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Boolean indication of whether the heap is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Merge all elements of `other` into this heap, in O(log n) time.
+    /// [Handle]s into `other` remain valid, and now address elements of `self`.
+    pub fn meld(&mut self, mut other: Self) {
+        Owner::absorb(&self.owner, &other.owner);
+        self.len += other.len;
+        other.len = 0;
+        let roots = std::mem::take(&mut other.roots);
+        self.merge_roots(roots);
+    }
+    /// Raise the priority of the element at `handle`, replacing its value with `t`.
+    ///
+    /// Returns an [Error] if `handle` is no longer valid (i.e. its element has been popped),
+    /// if it was issued by another heap (other than one since melded into this one),
+    /// or if `t` would *lower* the element's priority.
+    pub fn decrease_key(&mut self, handle: &Handle<T>, t: T) -> Result<(), Error> {
+        let mut node = handle.0.borrow().upgrade().ok_or(Error)?;
+        if !Owner::is_owned_by(&node.borrow().owner, &self.owner) {
+            return Err(Error);
+        }
+        if self.cmp.compare(&t, &node.borrow().data) == Ordering::Greater {
+            return Err(Error);
+        }
+        node.borrow_mut().data = t;
+
+        // Sift upward, swapping data (and slots) with each parent which it out-ranks
+        loop {
+            let parent = match node.borrow().parent.as_ref().and_then(Weak::upgrade) {
+                None => break, // Reached a root. Done.
+                Some(p) => p,
+            };
+            if self.cmp.compare(&node.borrow().data, &parent.borrow().data) != Ordering::Less {
+                break; // In order. Done.
+            }
+            {
+                let mut n = node.borrow_mut();
+                let mut p = parent.borrow_mut();
+                std::mem::swap(&mut n.data, &mut p.data);
+                std::mem::swap(&mut n.slot, &mut p.slot);
+                *n.slot.borrow_mut() = Rc::downgrade(&node);
+                *p.slot.borrow_mut() = Rc::downgrade(&parent);
+            }
+            node = parent;
+        }
+        Ok(())
+    }
+    /// Get the order of the top-priority root. Returns `None` if empty.
+    fn top_order(&self) -> Option<usize> {
+        let mut best: Option<(usize, &NodePtr<T>)> = None;
+        for (order, root) in self.roots.iter().enumerate() {
+            if let Some(root) = root {
+                best = match best {
+                    Some((_, b))
+                        if self.cmp.compare(&root.borrow().data, &b.borrow().data)
+                            != Ordering::Less =>
+                    {
+                        best
+                    }
+                    _ => Some((order, root)),
+                };
+            }
+        }
+        best.map(|(order, _)| order)
+    }
+    /// Merge the order-indexed list of trees `other` into our own, carrying as in binary addition
+    fn merge_roots(&mut self, other: Vec<Option<NodePtr<T>>>) {
+        let mut carry: Option<NodePtr<T>> = None;
+        let mut other = other.into_iter();
+        let mut order = 0;
+        loop {
+            let theirs = other.next();
+            if theirs.is_none() && carry.is_none() && order >= self.roots.len() {
+                break; // Nothing left to add. Done.
+            }
+            if order >= self.roots.len() {
+                self.roots.push(None);
+            }
+            // Gather up to three trees of order `order`: ours, theirs, and the carry
+            let trees: Vec<NodePtr<T>> = self.roots[order]
+                .take()
+                .into_iter()
+                .chain(theirs.flatten())
+                .chain(carry.take())
+                .collect();
+            let mut trees = trees.into_iter();
+            match (trees.next(), trees.next(), trees.next()) {
+                (None, _, _) => (),
+                (Some(a), None, _) => self.roots[order] = Some(a),
+                (Some(a), Some(b), None) => carry = Some(self.link(a, b)),
+                (Some(a), Some(b), Some(c)) => {
+                    self.roots[order] = Some(c);
+                    carry = Some(self.link(a, b));
+                }
+            }
+            order += 1;
+        }
+        self.trim();
+    }
+    /// Link two trees `a` and `b` of equal order.
+    /// The lower-priority of the two becomes the last child of the other, which is returned.
+    fn link(&self, a: NodePtr<T>, b: NodePtr<T>) -> NodePtr<T> {
+        let (winner, loser) =
+            if self.cmp.compare(&b.borrow().data, &a.borrow().data) == Ordering::Less {
+                (b, a)
+            } else {
+                (a, b)
+            };
+        loser.borrow_mut().parent = Some(Rc::downgrade(&winner));
+        winner.borrow_mut().children.push(loser);
+        winner
+    }
+    /// Remove empty trailing entries from `roots`
+    fn trim(&mut self) {
+        while let Some(None) = self.roots.last() {
+            self.roots.pop();
+        }
+    }
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T> for BinomialHeap<T, C> {
+    type Peek<'a>
+        = Ref<'a, T>
+    where
+        Self: 'a;
+
+    fn push(&mut self, t: T) {
+        BinomialHeap::push(self, t);
+    }
+    fn pop(&mut self) -> Option<T> {
+        BinomialHeap::pop(self)
+    }
+    fn peek(&self) -> Option<Ref<'_, T>> {
+        BinomialHeap::peek(self)
+    }
+    fn len(&self) -> usize {
+        self.len
+    }
+    fn meld(&mut self, other: Self) {
+        BinomialHeap::meld(self, other)
+    }
+}
+
+/// Local Error Type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error;
+
+#[test]
+fn test_binomial_heap() {
+    let mut heap = BinomialHeap::new();
+    for k in [10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5] {
+        heap.push(k);
+    }
+    assert_eq!(heap.len(), 11);
+    assert_eq!(*heap.peek().unwrap(), 0);
+    // Eleven elements, `0b1011`, make for trees of orders 0, 1, and 3
+    assert_eq!(heap.roots.len(), 4);
+    assert!(heap.roots[2].is_none());
+
+    let mut popped = Vec::new();
+    while let Some(k) = heap.pop() {
+        popped.push(k);
+    }
+    assert_eq!(popped, (0..=10).collect::<Vec<_>>());
+    assert!(heap.is_empty());
+    assert!(heap.roots.is_empty());
+}
+
+#[test]
+fn test_binomial_meld() {
+    use crate::heap::drain_sorted;
+
+    let mut a = BinomialHeap::new();
+    let mut b = BinomialHeap::new();
+    for k in 0..50 {
+        a.push(2 * k);
+        b.push(2 * k + 1);
+    }
+    a.meld(b);
+    assert_eq!(a.len(), 100);
+    assert_eq!(drain_sorted(&mut a), (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_binomial_decrease_key() -> Result<(), Error> {
+    let mut heap = BinomialHeap::new();
+    let handles: Vec<_> = (0..20).map(|k| heap.push(k * 10)).collect();
+
+    // Move a few elements toward the top
+    heap.decrease_key(&handles[15], 5)?;
+    heap.decrease_key(&handles[7], -1)?;
+    assert_eq!(*heap.peek().unwrap(), -1);
+    // Lowering priority is an error
+    assert_eq!(heap.decrease_key(&handles[3], 100), Err(Error));
+
+    assert_eq!(heap.pop(), Some(-1));
+    assert_eq!(heap.pop(), Some(0));
+    assert_eq!(heap.pop(), Some(5));
+    assert_eq!(heap.pop(), Some(10));
+
+    // Popped elements' handles are invalid
+    assert_eq!(heap.decrease_key(&handles[0], -5), Err(Error));
+    assert_eq!(heap.len(), 16);
+
+    // Handles survive both sifting and a `meld`
+    heap.decrease_key(&handles[19], 15)?;
+    heap.decrease_key(&handles[19], 11)?;
+    let mut other = BinomialHeap::new();
+    let h = other.push(1000);
+    heap.meld(other);
+    heap.decrease_key(&h, 1)?;
+    assert_eq!(heap.pop(), Some(1));
+    assert_eq!(heap.pop(), Some(11));
+    assert_eq!(heap.pop(), Some(20));
+
+    // Handles from other heaps are rejected, including after either is melded elsewhere
+    let mut other = BinomialHeap::new();
+    let h = other.push(1000);
+    assert_eq!(heap.decrease_key(&h, 0), Err(Error));
+    let mut third = BinomialHeap::new();
+    third.meld(other);
+    assert_eq!(heap.decrease_key(&h, 0), Err(Error));
+    third.meld(heap);
+    assert_eq!(third.decrease_key(&handles[18], 0), Ok(()));
+    assert_eq!(third.decrease_key(&h, -1), Ok(()));
+    assert_eq!(third.pop(), Some(-1));
+    Ok(())
+}
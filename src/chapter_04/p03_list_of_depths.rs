@@ -7,44 +7,29 @@
 //! Hints: #107, #123, #735
 //!
 
-use crate::{
-    binary_tree::{BinaryTree, NodePtr as BtreePtr},
-    ptr_list::List,
-};
+use crate::{ptr_list::List, tree_view::TreeView};
 
 /// Primary Implementation
 ///
-/// In-order traverse tree nodes, keeping track of the depth at each,
-/// and appending each node encountered to a linked-list indexed with its depth.
+/// Walk the tree level by level, collecting each level's data into a linked-list.
 ///
-pub fn list_of_depths(tree: &BinaryTree<isize>) -> Vec<List> {
-    let mut results = Vec::new();
-    helper(&tree.head, 0, &mut results);
-    results
-}
-
-/// Recursive helper, doing all the real work
-fn helper(ptr: &Option<BtreePtr<isize>>, level: usize, results: &mut Vec<List>) {
-    // Unwrap the option around `ptr`, returning if it is `None`.
-    let ptr = match ptr {
-        None => return, // Base case: no node, no children
-        Some(_) => ptr.as_ref().unwrap(),
-    };
-
-    // If it's our first time on this level, add an empty list to `results`.
-    if results.len() <= level {
-        results.push(List::default());
-    }
-
-    // In-order traverse nodes, adding to the linked-list at their `level`
-    helper(&ptr.borrow().left, level + 1, results);
-    results[level].add(ptr.borrow().data);
-    helper(&ptr.borrow().right, level + 1, results);
+pub fn list_of_depths<V: TreeView<Data = isize> + ?Sized>(tree: &V) -> Vec<List> {
+    tree.levels()
+        .map(|level| {
+            let mut list = List::default();
+            for node in level {
+                list.add(*tree.data(&node));
+            }
+            list
+        })
+        .collect()
 }
 
 #[test]
 fn test_list_of_depths() {
-    use crate::{binary_tree::Node as BtreeNode, ptr::Ptr};
+    use crate::arena_tree::ArenaTree;
+    use crate::binary_tree::{BinaryTree, Node as BtreeNode};
+    use crate::ptr::Ptr;
 
     let node_h = Ptr::new(BtreeNode::new(7, None, None));
     let node_g = Ptr::new(BtreeNode::new(6, None, None));
@@ -62,12 +47,17 @@ fn test_list_of_depths() {
         Some(node_b.clone()),
         Some(node_c.clone()),
     ));
-    let lists = list_of_depths(&BinaryTree {
+    let tree = BinaryTree {
         head: Some(node_a.clone()),
-    });
+    };
+    let lists = list_of_depths(&tree);
 
     assert_eq!(lists[0].to_vec(), vec![0]);
     assert_eq!(lists[1].to_vec(), vec![1, 2]);
     assert_eq!(lists[2].to_vec(), vec![3, 4, 5]);
     assert_eq!(lists[3].to_vec(), vec![7, 6]);
+
+    // Same again, on an arena-based copy
+    let lists = list_of_depths(&ArenaTree::from(&tree));
+    assert_eq!(lists[3].to_vec(), vec![7, 6]);
 }
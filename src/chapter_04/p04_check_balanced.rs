@@ -8,50 +8,32 @@
 //! Hints: #27, #33, #49, #705, #724
 //!
 
-use crate::binary_tree::{BinaryTree, NodePtr};
+use crate::tree_view::TreeView;
 
 /// Primary Implementation
 ///
-/// Recursively check the balance and height of each sub-tree.
+/// Post-order traverse the tree, so that each node's sub-trees are visited just before it.
+/// Their heights are then the top entries of a stack, which each node pops and replaces with its own,
+/// failing as soon as any node's sub-trees differ in height by more than one.
 ///
-pub fn check_balanced(tree: &BinaryTree<isize>) -> bool {
-    // Kick off recursive calls.
-    //
-    // While our helper also returns the tree-height if balanced,
-    // we only care about the boolean indication of its balance,
-    // indicated by its returning `Some(_)`.
-    helper(&tree.head).is_some()
-}
-
-/// Recursive helper.
-/// Returns the height of the tree beginning with `ptr`, if that sub-tree is balanced.
-/// Returns `None` if it is not balanced.
-fn helper(ptr: &Option<NodePtr<isize>>) -> Option<usize> {
-    // Get the contents of our node-pointer, or return if null
-    let ptr = match ptr {
-        None => return Some(0), // Base case: no node
-        Some(ref p) => p,       // Node here, keep going
-    };
-
-    // Get the heights of left and right sub-trees, if balanced.
-    // Note the question-mark operators early-return `None` if not.
-    let node = ptr.borrow();
-    let left_depth = helper(&node.left)?;
-    let right_depth = helper(&node.right)?;
-
-    // And sort out whether the two are balanced
-    let max = left_depth.max(right_depth);
-    let min = left_depth.min(right_depth);
-    if max - min < 2 {
-        Some(max + 1)
-    } else {
-        None
+pub fn check_balanced<V: TreeView + ?Sized>(tree: &V) -> bool {
+    let mut heights: Vec<usize> = Vec::new();
+    for node in tree.iter_postorder() {
+        // Pop in reverse visit-order: right, then left
+        let right = tree.right(&node).map_or(0, |_| heights.pop().unwrap());
+        let left = tree.left(&node).map_or(0, |_| heights.pop().unwrap());
+        if left.abs_diff(right) > 1 {
+            return false;
+        }
+        heights.push(left.max(right) + 1);
     }
+    true
 }
 
 #[test]
 fn test_check_balanced() {
-    use crate::binary_tree::Node;
+    use crate::arena_tree::ArenaTree;
+    use crate::binary_tree::{BinaryTree, Node, NodePtr};
 
     let balanced1 = || {
         let left = NodePtr::new(Node::new(2, None, None));
@@ -115,8 +97,22 @@ fn test_check_balanced() {
         (unbalanced1(), false),
         (unbalanced2(), false),
     ];
-    for case in test_cases {
+    for case in test_cases.iter() {
         dbg!(&case.0);
         assert_eq!(check_balanced(&case.0), case.1);
     }
+
+    // The same trees, in level-order text, and as [ArenaTree]s
+    let srcs = [
+        "[1,2]",
+        "[7,2,3,4,null,8,9,null,null,null,null,null,10]",
+        "[1,2,3,4,5,8,9,null,null,null,6,null,null,null,10,null,7,null,11]",
+        "[1,2,9,3,7,10,null,6,null,12,5,null,null,null,null,16,0]",
+    ];
+    for (src, (fixture, balanced)) in srcs.iter().zip(test_cases.iter()) {
+        let tree: BinaryTree<isize> = BinaryTree::deserialize(src).unwrap();
+        assert_eq!(tree.serialize(), fixture.serialize());
+        assert_eq!(check_balanced(&tree), *balanced);
+        assert_eq!(check_balanced(&ArenaTree::from(&tree)), *balanced);
+    }
 }
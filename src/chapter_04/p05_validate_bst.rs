@@ -6,35 +6,45 @@
 //! Hints: #35, #57, #86, # 773, # 728
 //!
 
-use crate::binary_tree::{BinaryTree, NodePtr};
+use crate::tree_view::TreeView;
 
 /// Primary Implementation
 ///
 /// Tracking a maximum and minimum valid values as we go, recurse down the tree,
 /// checking that each sub-tree meets the BST properties.
 ///
-pub fn validate_bst(tree: &BinaryTree<isize>) -> bool {
-    helper(&tree.head, isize::MIN, isize::MAX)
+pub fn validate_bst<V>(tree: &V) -> bool
+where
+    V: TreeView + ?Sized,
+    V::Data: Ord,
+{
+    helper(tree, tree.root(), None, None)
 }
 
 /// Recursive helper.
-/// Return whether `min` < `ptr.data` < `max`, and that each of `ptr`'s sub-trees also meets the BST properties.
-fn helper(ptr: &Option<NodePtr<isize>>, min: isize, max: isize) -> bool {
-    let ptr = match ptr {
+/// Return whether `min` < `node.data` < `max`, and that each of `node`'s sub-trees also meets the BST properties.
+/// Bounds of `None` are unlimited.
+fn helper<V>(tree: &V, node: Option<V::Node>, min: Option<&V::Data>, max: Option<&V::Data>) -> bool
+where
+    V: TreeView + ?Sized,
+    V::Data: Ord,
+{
+    let node = match node {
         None => return true,
-        Some(ref p) => p,
+        Some(n) => n,
     };
     // Check that each sub-tree, and the node itself, meet the BST properties
-    let node = ptr.borrow();
-    node.data < max
-        && node.data > min
-        && helper(&node.left, min, node.data)
-        && helper(&node.right, node.data, max)
+    let data = tree.data(&node);
+    max.map_or(true, |max| *data < *max)
+        && min.map_or(true, |min| *data > *min)
+        && helper(tree, tree.left(&node), min, Some(&data))
+        && helper(tree, tree.right(&node), Some(&data), max)
 }
 
 #[test]
 fn test_validate_bst() {
-    use crate::binary_tree::Node;
+    use crate::arena_tree::ArenaTree;
+    use crate::binary_tree::{BinaryTree, Node, NodePtr};
 
     let tree = BinaryTree {
         head: Some(NodePtr::new(Node::new(
@@ -53,6 +63,7 @@ fn test_validate_bst() {
     };
 
     assert_eq!(validate_bst(&tree), true);
+    assert!(validate_bst(&ArenaTree::from(&tree)));
 
     let tree = BinaryTree {
         head: Some(NodePtr::new(Node::new(
@@ -75,4 +86,5 @@ fn test_validate_bst() {
     };
 
     assert_eq!(validate_bst(&tree), false);
+    assert!(!validate_bst(&ArenaTree::from(&tree)));
 }
@@ -25,13 +25,13 @@ pub fn successor(tree: &BinarySearchTree, val: isize) -> Option<isize> {
     // If that node has a right sub-tree, return its leftmost element
     if let Some(right) = tree[idx].right {
         let succ = leftmost(tree, right);
-        return Some(tree[succ].data);
+        return Some(tree[succ].key);
     }
 
     // No right sub-tree. The successor is the next parent which we hit coming from the left.
     while let Some(pidx) = tree[idx].parent {
-        if tree[pidx].data > tree[idx].data {
-            return Some(tree[pidx].data);
+        if tree[pidx].key > tree[idx].key {
+            return Some(tree[pidx].key);
         }
         idx = pidx;
     }
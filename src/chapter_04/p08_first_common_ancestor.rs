@@ -7,7 +7,7 @@
 //! Hints: # 10, #16, #28, #36, #46, #70, #80, #96
 //!
 
-use crate::binary_tree::{BinaryTree, NodePtr};
+use crate::tree_view::TreeView;
 
 /// Primary Implementation
 ///
@@ -15,13 +15,13 @@ use crate::binary_tree::{BinaryTree, NodePtr};
 /// from among solutions *without* parent-pointers.
 /// It is modified to use a Rust-idiomatic `enum` type to indicate results of searches from sub-trees.
 ///
-pub fn first_common_ancestor(
-    tree: &BinaryTree<isize>,
-    p: &NodePtr<isize>,
-    q: &NodePtr<isize>,
-) -> Option<NodePtr<isize>> {
+pub fn first_common_ancestor<V: TreeView + ?Sized>(
+    tree: &V,
+    p: &V::Node,
+    q: &V::Node,
+) -> Option<V::Node> {
     // Search for the two nodes, generating a [SubTree] result
-    let subtree = helper(&tree.head, p, q);
+    let subtree = helper(tree, tree.root(), p, q);
 
     // And return the ancestor-node, only if it found both
     if let SubTree::HasBoth(a) = subtree {
@@ -34,45 +34,49 @@ pub fn first_common_ancestor(
 /// Recursive helper
 ///
 /// Create a [SubTree] result indicating either a common ancestor, or the presence/ absence of `p` and `q`.
-fn helper(root: &Option<NodePtr<isize>>, p: &NodePtr<isize>, q: &NodePtr<isize>) -> SubTree {
+fn helper<V: TreeView + ?Sized>(
+    tree: &V,
+    root: Option<V::Node>,
+    p: &V::Node,
+    q: &V::Node,
+) -> SubTree<V::Node> {
     use SubTree::{HasBoth, HasNeither, HasP, HasQ};
 
-    // Unwrap the root-node pointer-option. Return neither-found if it is `None`.
+    // Unwrap the root-node option. Return neither-found if it is `None`.
     let root = match root {
         None => return HasNeither,
-        Some(ref r) => r,
+        Some(r) => r,
     };
 
     // Search the node's left and right sub-trees
-    let node = root.borrow();
-    let left = helper(&node.left, p, q);
-    let right = helper(&node.right, p, q);
+    let left = helper(tree, tree.left(&root), p, q);
+    let right = helper(tree, tree.right(&root), p, q);
 
     // And combine the two
     match (left, right) {
         (HasBoth(n), _) | (_, HasBoth(n)) => HasBoth(n), // Found in a sub-tree
-        (HasQ, HasP) | (HasP, HasQ) => HasBoth(root.clone()), // `root` is it
+        (HasQ, HasP) | (HasP, HasQ) => HasBoth(root),    // `root` is it
         (HasP, HasNeither) | (HasNeither, HasP) => {
             // `p` was found in a sub-tree. Convert to `HasBoth` if root is `q`.
-            if root == q {
-                HasBoth(root.clone())
+            if root == *q {
+                HasBoth(root)
             } else {
                 HasP
             }
         }
         (HasQ, HasNeither) | (HasNeither, HasQ) => {
             // `q` was found in a sub-tree. Convert to `HasBoth` if root is `p`.
-            if root == p {
-                HasBoth(root.clone())
+            if root == *p {
+                HasBoth(root)
             } else {
                 HasQ
             }
         }
         (HasNeither, HasNeither) => {
             // Neither found. Check `root`
-            if root == q {
+            if root == *q {
                 HasQ
-            } else if root == p {
+            } else if root == *p {
                 HasP
             } else {
                 HasNeither
@@ -81,18 +85,18 @@ fn helper(root: &Option<NodePtr<isize>>, p: &NodePtr<isize>, q: &NodePtr<isize>)
         _ => unreachable!("Internal Error"),
     }
 }
-/// Enumerated Results for searching a sub-tree
+/// Enumerated Results for searching a sub-tree, generic over node-handle type `N`
 #[derive(Debug, PartialEq, Eq)]
-pub enum SubTree {
+pub enum SubTree<N> {
     HasNeither,
     HasP,
     HasQ,
-    HasBoth(NodePtr<isize>),
+    HasBoth(N),
 }
 
 #[test]
 fn test_first_common_ancestor() {
-    use crate::binary_tree::Node;
+    use crate::binary_tree::{BinaryTree, Node, NodePtr};
 
     let n8 = NodePtr::new(Node::new(8, None, None));
     let n7 = NodePtr::new(Node::new(8, None, None));
@@ -107,3 +111,22 @@ fn test_first_common_ancestor() {
 
     assert_eq!(first_common_ancestor(&tree, &n3, &n4), Some(n1));
 }
+
+#[test]
+fn test_first_common_ancestor_arena() -> Result<(), crate::binary_tree::Error> {
+    use crate::arena_tree::ArenaTree;
+
+    let mut tree = ArenaTree::new();
+    let n1 = tree.insert(1, None)?;
+    let n2 = tree.insert(2, Some(n1))?;
+    let n3 = tree.insert(3, Some(n1))?;
+    let n4 = tree.insert(4, Some(n2))?;
+    let n5 = tree.insert(5, Some(n2))?;
+    let n7 = tree.insert(7, Some(n3))?;
+    let n8 = tree.insert(8, Some(n4))?;
+
+    assert_eq!(first_common_ancestor(&tree, &n3, &n4), Some(n1));
+    assert_eq!(first_common_ancestor(&tree, &n8, &n5), Some(n2));
+    assert_eq!(first_common_ancestor(&tree, &n7, &n3), Some(n3));
+    Ok(())
+}
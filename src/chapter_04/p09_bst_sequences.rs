@@ -44,7 +44,7 @@ fn helper(tree: &BinarySearchTree, ptr: &Option<NodeIndex>) -> Vec<Vec<isize>> {
     }
 
     // Finally prepend the node's value to each list in `results`
-    prepend(&[tree[idx].data], results)
+    prepend(&[tree[idx].key], results)
 }
 
 /// Weave together all combinations of lists `a` and `b`, retaining the ordering among elements within `a` and `b`.
@@ -92,15 +92,24 @@ fn test_bst_sequences() {
     bst.insert(12);
 
     let sequences = bst_sequences(&bst);
+    // Inserted at leaves: 20 at the head, with 9 (and its children 5 and 12) to its left, and 25 to its right
     let correct = [
-        [20, 12, 9, 5, 25],
-        [20, 12, 9, 25, 5],
-        [20, 12, 9, 5, 25],
-        [20, 12, 25, 9, 5],
-        [20, 12, 9, 5, 25],
-        [20, 12, 9, 25, 5],
-        [20, 12, 9, 5, 25],
-        [20, 25, 12, 9, 5],
+        [20, 9, 5, 12, 25],
+        [20, 9, 5, 25, 12],
+        [20, 9, 5, 12, 25],
+        [20, 9, 25, 5, 12],
+        [20, 9, 5, 12, 25],
+        [20, 9, 5, 25, 12],
+        [20, 9, 5, 12, 25],
+        [20, 25, 9, 5, 12],
+        [20, 9, 12, 5, 25],
+        [20, 9, 12, 25, 5],
+        [20, 9, 12, 5, 25],
+        [20, 9, 25, 12, 5],
+        [20, 9, 12, 5, 25],
+        [20, 9, 12, 25, 5],
+        [20, 9, 12, 5, 25],
+        [20, 25, 9, 12, 5],
     ];
     assert_eq!(sequences, &correct);
 }
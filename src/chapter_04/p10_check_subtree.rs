@@ -10,41 +10,62 @@
 //! Hints: #4, #77, #78, #37, #37
 //!
 
+use crate::tree_view::TreeView;
 #[cfg(test)]
-use crate::binary_tree::Error;
-use crate::binary_tree::{BinaryTree, NodePtr};
+use crate::{
+    arena_tree::ArenaTree,
+    binary_tree::{BinaryTree, Error},
+};
 
 /// Primary Implementation
 ///
 /// Search `t1` for nodes equal to `t2`'s root.
 /// Upon finding one, compare all remaining nodes in their sub-trees.
+/// The two trees may be of different [TreeView] implementations.
 ///
-pub fn check_subtree<T: Eq>(t1: &BinaryTree<T>, t2: &BinaryTree<T>) -> bool {
-    search(&t1.head, &t2.head)
+pub fn check_subtree<A, B>(t1: &A, t2: &B) -> bool
+where
+    A: TreeView + ?Sized,
+    B: TreeView<Data = A::Data> + ?Sized,
+    A::Data: Eq,
+{
+    search(t1, t1.root(), t2, t2.root())
 }
 // Search for `targ`'s root-node. Then compare sub-trees the rest of the way down.
-fn search<T: Eq>(tree: &Option<NodePtr<T>>, targ: &Option<NodePtr<T>>) -> bool {
-    match (tree.as_ref(), targ.as_ref()) {
+fn search<A, B>(t1: &A, tree: Option<A::Node>, t2: &B, targ: Option<B::Node>) -> bool
+where
+    A: TreeView + ?Sized,
+    B: TreeView<Data = A::Data> + ?Sized,
+    A::Data: Eq,
+{
+    match (tree, targ) {
         (Some(tr), Some(ta)) => {
             // If we hit the root value, compare the rest
-            if tr.borrow().data == ta.borrow().data && compare(tree, targ) {
+            if *t1.data(&tr) == *t2.data(&ta) && compare(t1, Some(tr.clone()), t2, Some(ta.clone()))
+            {
                 true
             } else {
                 // Otherwise, keep searching for it.
-                search(&tr.borrow().left, targ) || search(&tr.borrow().right, targ)
+                search(t1, t1.left(&tr), t2, Some(ta.clone()))
+                    || search(t1, t1.right(&tr), t2, Some(ta))
             }
         }
         (_, None) => true,  // Null nodes count as a sub-tree of any tree
         (None, _) => false, // No tree to search, no hits
     }
 }
-/// Compare (sub)trees `t1` and `t2`
-fn compare<T: Eq>(t1: &Option<NodePtr<T>>, t2: &Option<NodePtr<T>>) -> bool {
-    match (t1.as_ref(), t2.as_ref()) {
-        (Some(t1), Some(t2)) => {
-            t1.borrow().data == t2.borrow().data // Two nodes must be equal, and 
-                && compare(&t1.borrow().left, &t2.borrow().left)   // Left sub-tree must be equal, and 
-                && compare(&t1.borrow().right, &t2.borrow().right) // Right sub-tree must be equal
+/// Compare the (sub)trees of `t1` from `n1` and of `t2` from `n2`
+fn compare<A, B>(t1: &A, n1: Option<A::Node>, t2: &B, n2: Option<B::Node>) -> bool
+where
+    A: TreeView + ?Sized,
+    B: TreeView<Data = A::Data> + ?Sized,
+    A::Data: Eq,
+{
+    match (n1, n2) {
+        (Some(n1), Some(n2)) => {
+            *t1.data(&n1) == *t2.data(&n2) // Two nodes must be equal, and 
+                && compare(t1, t1.left(&n1), t2, t2.left(&n2))   // Left sub-tree must be equal, and 
+                && compare(t1, t1.right(&n1), t2, t2.right(&n2)) // Right sub-tree must be equal
         }
         (None, None) => true, // Both null is a match
         _ => false,           // Either other case (Some, None) | (None, Some) is a mismatch
@@ -77,5 +98,12 @@ fn test_check_subtree() -> Result<(), Error> {
     t4.insert(111, None)?;
     assert_eq!(check_subtree(&t1, &t4), false);
 
+    // Either tree may be arena-based
+    let a1 = ArenaTree::from(&t1);
+    let a2 = ArenaTree::from(&t2);
+    assert!(check_subtree(&a1, &a2));
+    assert!(check_subtree(&a1, &t3));
+    assert!(!check_subtree(&t1, &ArenaTree::from(&t4)));
+
     Ok(())
 }
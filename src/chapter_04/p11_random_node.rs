@@ -13,29 +13,44 @@
 // Each node keeps track of the size of its sub-tree.
 // A random integer between 0 and `root.size` dictates which node
 // is randomly selected.
+//
+// Weighted selection works the same way, with each node also tracking
+// the total weight of its sub-tree, and a random integer between 0 and
+// the root's total weight dictating the selection.
 
 use std::ops::{Index, IndexMut};
 
+use rand::Rng;
+
 ///
 /// # (Yet) Another Binary Tree Node!
 ///
 /// This time with a size of its sub-tree.
+/// Also carries a sampling `weight`, and the total weight of its sub-tree, for weighted selection.
 ///
 #[derive(Debug)]
 pub struct Node {
     pub data: isize,
     pub size: usize,
+    pub weight: u64,
+    pub total_weight: u64,
     pub index: NodeIndex,
     pub left: Option<NodeIndex>,
     pub right: Option<NodeIndex>,
     pub parent: Option<NodeIndex>,
 }
 impl Node {
-    /// Create a new [Node] with no children, and no parent
+    /// Create a new [Node] with no children, and no parent, and unit weight
     pub fn orphan(data: isize, index: NodeIndex) -> Self {
+        Self::weighted_orphan(data, 1, index)
+    }
+    /// Create a new [Node] with no children, no parent, and weight `weight`
+    pub fn weighted_orphan(data: isize, weight: u64, index: NodeIndex) -> Self {
         Self {
             data,
             size: 1,
+            weight,
+            total_weight: weight,
             index,
             left: None,
             right: None,
@@ -45,7 +60,7 @@ impl Node {
 }
 
 /// Index Reference into a Tree
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct NodeIndex(usize);
 
 /// Binary Tree
@@ -67,23 +82,26 @@ impl IndexMut<NodeIndex> for BinarySearchTree {
 }
 
 impl BinarySearchTree {
-    /// Add a new [Node] with value `data`. Returns its index.
-    fn add_node(&mut self, data: isize) -> NodeIndex {
+    /// Add a new [Node] with value `data` and weight `weight`. Returns its index.
+    fn add_node(&mut self, data: isize, weight: u64) -> NodeIndex {
         let index = NodeIndex(self.nodes.len());
-        self.nodes.push(Node {
-            data,
-            index,
-            size: 1,
-            parent: None,
-            left: None,
-            right: None,
-        });
+        self.nodes.push(Node::weighted_orphan(data, weight, index));
         index
     }
-    /// Insert a new [Node] with value `val`. Returns its [NodeIndex].
+    /// Insert a new [Node] with value `val`, and unit weight. Returns its [NodeIndex].
     pub fn insert(&mut self, val: isize) -> Result<NodeIndex, Error> {
+        self.insert_weighted(val, 1)
+    }
+    /// Insert a new [Node] with value `val` and sampling-weight `weight`. Returns its [NodeIndex].
+    /// Returns an [Error] if the tree's total weight would overflow.
+    pub fn insert_weighted(&mut self, val: isize, weight: u64) -> Result<NodeIndex, Error> {
+        // Every sub-tree's total is at most the root's, so if its total fits, all do
+        self.total_weight(self.head)
+            .checked_add(weight)
+            .ok_or(Error)?;
+
         // Create and add the new node
-        let newindex = self.add_node(val);
+        let newindex = self.add_node(val, weight);
 
         let mut parent = match self.head {
             Some(p) => p,
@@ -95,6 +113,7 @@ impl BinarySearchTree {
         };
         loop {
             self[parent].size += 1;
+            self[parent].total_weight += weight;
             if val < self[parent].data {
                 // Look left
                 match self[parent].left {
@@ -136,31 +155,158 @@ impl BinarySearchTree {
         }
         None // Not found
     }
+    /// Remove the node with value `val`.
+    /// Returns an [Error] if not present.
+    /// See [BinarySearchTree::remove_node] for the effects on other indices.
+    pub fn remove(&mut self, val: isize) -> Result<(), Error> {
+        let idx = self.find(val).ok_or(Error)?;
+        self.remove_node(idx);
+        Ok(())
+    }
+    /// Remove node `idx` from the tree, returning its value.
+    ///
+    /// Storage is kept compact, by moving the last node into the freed slot.
+    /// This invalidates any outstanding [NodeIndex] to that node, or to `idx`.
+    pub fn remove_node(&mut self, idx: NodeIndex) -> isize {
+        let data = self[idx].data;
+
+        // With two children, take on the data and weight of our in-order successor,
+        // and remove it instead. It has no left child.
+        let target = match (self[idx].left, self[idx].right) {
+            (Some(_), Some(rt)) => {
+                let mut succ = rt;
+                while let Some(lf) = self[succ].left {
+                    succ = lf;
+                }
+                self[idx].data = self[succ].data;
+                self[idx].weight = self[succ].weight;
+                succ
+            }
+            _ => idx,
+        };
+
+        // Splice `target` out, replacing it with its single child, if any
+        let child = self[target].left.or(self[target].right);
+        let parent = self[target].parent;
+        if let Some(c) = child {
+            self[c].parent = parent;
+        }
+        match parent {
+            None => self.head = child,
+            Some(p) if self[p].left == Some(target) => self[p].left = child,
+            Some(p) => self[p].right = child,
+        }
+
+        // Update sizes and weights up to the root. Note these include `idx`,
+        // if it has taken on its successor's weight.
+        let mut node = parent;
+        while let Some(n) = node {
+            self[n].size -= 1;
+            self.update_weight(n);
+            node = self[n].parent;
+        }
+
+        self.swap_remove(target);
+        data
+    }
+    /// Set the sampling-weight of node `idx` to `weight`.
+    /// Returns an [Error], leaving the weight unchanged, if the tree's total weight would overflow.
+    pub fn set_weight(&mut self, idx: NodeIndex, weight: u64) -> Result<(), Error> {
+        (self.total_weight(self.head) - self[idx].weight)
+            .checked_add(weight)
+            .ok_or(Error)?;
+        self[idx].weight = weight;
+        let mut node = Some(idx);
+        while let Some(n) = node {
+            self.update_weight(n);
+            node = self[n].parent;
+        }
+        Ok(())
+    }
     /// Get a uniformly-distributed random [NodeIndex].
     /// Returns `None` if the tree is empty.
     pub fn get_random(&self) -> Option<NodeIndex> {
+        self.get_random_with(&mut rand::thread_rng())
+    }
+    /// Get a uniformly-distributed random [NodeIndex], drawing from random-number generator `rng`.
+    /// Seeded generators make for reproducible selections.
+    /// Returns `None` if the tree is empty.
+    pub fn get_random_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<NodeIndex> {
         // Check for emptiness, and get our root-node.
-        let head = match self.head {
-            None => return None,
-            Some(h) => h,
-        };
+        let head = self.head?;
 
         // Grab a random, uniformly-distributed integer,
         // within the bounds of our `head`'s size.
-        let random_num = {
-            use rand::distributions::{Distribution, Uniform};
-
-            let mut rng = rand::thread_rng();
-            let dist = Uniform::from(0..self[head].size);
-            dist.sample(&mut rng)
-        };
+        let random_num = rng.gen_range(0..self[head].size);
 
         // And kick off the recursive search for the `random_num`-th
-        // node in an in-order traversal.
+        // node in a pre-order traversal.
         Some(self.random_helper(head, random_num))
     }
+    /// Get a random [NodeIndex], with each node's probability proportional to its weight.
+    /// Returns `None` if the tree is empty, or has zero total weight.
+    pub fn get_random_weighted(&self) -> Option<NodeIndex> {
+        self.get_random_weighted_with(&mut rand::thread_rng())
+    }
+    /// Get a random [NodeIndex], with each node's probability proportional to its weight,
+    /// drawing from random-number generator `rng`.
+    /// Returns `None` if the tree is empty, or has zero total weight.
+    pub fn get_random_weighted_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<NodeIndex> {
+        let head = self.head?;
+        if self[head].total_weight == 0 {
+            return None;
+        }
+        let mut target = rng.gen_range(0..self[head].total_weight);
+
+        // Walk down the tree, peeling off the weight of each sub-tree we skip past.
+        // Zero-weight nodes take up no space in this range, and so are never selected.
+        let mut node = head;
+        loop {
+            let left_weight = self.total_weight(self[node].left);
+            if target < left_weight {
+                node = self[node].left.unwrap();
+                continue;
+            }
+            target -= left_weight;
+            if target < self[node].weight {
+                return Some(node);
+            }
+            target -= self[node].weight;
+            node = self[node].right.unwrap();
+        }
+    }
+    /// Get the total weight of optional sub-tree `opt`
+    fn total_weight(&self, opt: Option<NodeIndex>) -> u64 {
+        opt.map_or(0, |idx| self[idx].total_weight)
+    }
+    /// Recompute the total weight of `idx`'s sub-tree from its children
+    fn update_weight(&mut self, idx: NodeIndex) {
+        self[idx].total_weight = self[idx].weight
+            + self.total_weight(self[idx].left)
+            + self.total_weight(self[idx].right);
+    }
+    /// Remove un-linked node `idx` from storage, moving the last node into its slot
+    fn swap_remove(&mut self, idx: NodeIndex) {
+        let last = NodeIndex(self.nodes.len() - 1);
+        if idx != last {
+            // Re-point everything referring to `last` at `idx`
+            let (parent, left, right) = (self[last].parent, self[last].left, self[last].right);
+            match parent {
+                None => self.head = Some(idx),
+                Some(p) if self[p].left == Some(last) => self[p].left = Some(idx),
+                Some(p) => self[p].right = Some(idx),
+            }
+            for c in [left, right].iter().flatten() {
+                self[*c].parent = Some(idx);
+            }
+        }
+        self.nodes.swap_remove(idx.0);
+        if idx != last {
+            self[idx].index = idx;
+        }
+    }
     /// Recursive helper for `get_random`.
-    /// Returns the `rand`-th in-order node of the sub-tree headed by `node`.
+    /// Returns the `rand`-th pre-order node of the sub-tree headed by `node`.
     /// Panics if the sub-tree has less than `rand` nodes.
     fn random_helper(&self, node: NodeIndex, rand: usize) -> NodeIndex {
         if rand == 0 {
@@ -250,3 +396,104 @@ fn test_get_random_node() -> Result<(), Error> {
     }
     Ok(())
 }
+
+#[test]
+fn test_remove() -> Result<(), Error> {
+    let mut bst = BinarySearchTree::default();
+    for val in [20, 9, 25, 5, 12, 11, 14] {
+        bst.insert(val)?;
+    }
+    // Leaf, single-child, two-child, and root removals
+    bst.remove(5)?;
+    bst.remove(12)?;
+    assert_eq!(bst.remove(12), Err(Error));
+    bst.remove(20)?;
+
+    assert_eq!(bst.nodes.len(), 4);
+    let head = bst.head.unwrap();
+    assert_eq!(bst[head].data, 25);
+    assert_eq!(bst[head].size, 4);
+    assert_eq!(bst[head].total_weight, 4);
+    for val in [9, 11, 14, 25] {
+        let idx = bst.find(val).unwrap();
+        assert_eq!(bst[idx].index, idx);
+        let left = bst[idx].left.map_or(0, |c| bst[c].size);
+        let right = bst[idx].right.map_or(0, |c| bst[c].size);
+        assert_eq!(bst[idx].size, 1 + left + right);
+    }
+    for val in [9, 11, 14, 25] {
+        bst.remove(val)?;
+    }
+    assert!(bst.head.is_none());
+    assert_eq!(bst.get_random(), None);
+    Ok(())
+}
+
+#[test]
+fn test_seeded_random() -> Result<(), Error> {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut bst = BinarySearchTree::default();
+    for val in [20, 9, 25, 5, 12, 11, 14] {
+        bst.insert(val)?;
+    }
+    bst.remove(9)?;
+
+    // Same seed, same selections
+    let mut a = StdRng::seed_from_u64(42);
+    let mut b = StdRng::seed_from_u64(42);
+    for _ in 0..20 {
+        assert_eq!(bst.get_random_with(&mut a), bst.get_random_with(&mut b));
+    }
+    // And every node gets selected
+    let mut counts = vec![0; bst.nodes.len()];
+    for _ in 0..600 {
+        counts[bst.get_random_with(&mut a).unwrap().0] += 1;
+    }
+    assert!(counts.iter().all(|&c| c > 50));
+    Ok(())
+}
+
+#[test]
+fn test_weighted_random() -> Result<(), Error> {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut bst = BinarySearchTree::default();
+    let heavy = bst.insert_weighted(10, 8)?;
+    bst.insert_weighted(5, 1)?;
+    bst.insert_weighted(15, 1)?;
+    let never = bst.insert_weighted(20, 0)?;
+    assert_eq!(bst[bst.head.unwrap()].total_weight, 10);
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut counts = vec![0; bst.nodes.len()];
+    for _ in 0..1000 {
+        counts[bst.get_random_weighted_with(&mut rng).unwrap().0] += 1;
+    }
+    assert_eq!(counts[never.0], 0);
+    assert!(counts[heavy.0] > 700);
+
+    // Re-weighting, and removing the heavy node
+    bst.set_weight(never, 2)?;
+    assert_eq!(bst[bst.head.unwrap()].total_weight, 12);
+    bst.remove(10)?;
+    assert_eq!(bst[bst.head.unwrap()].total_weight, 4);
+    assert!(bst.get_random_weighted_with(&mut rng).is_some());
+
+    let mut zero = BinarySearchTree::default();
+    zero.insert_weighted(1, 0)?;
+    assert_eq!(zero.get_random_weighted(), None);
+
+    // Total weights which would overflow are rejected, leaving the tree unchanged
+    let mut big = BinarySearchTree::default();
+    let first = big.insert_weighted(1, u64::MAX - 1)?;
+    assert_eq!(big.insert_weighted(2, 2), Err(Error));
+    let second = big.insert_weighted(2, 1)?;
+    assert_eq!(big.set_weight(second, 2), Err(Error));
+    assert_eq!(big.nodes.len(), 2);
+    assert_eq!(big[first].total_weight, u64::MAX);
+    big.set_weight(first, 5)?;
+    big.set_weight(second, u64::MAX - 5)?;
+    assert_eq!(big[first].total_weight, u64::MAX);
+    Ok(())
+}
@@ -8,9 +8,12 @@
 //! Hints: #6, #74, #52, #68, #77, #87, #94, #703, #708, #115
 //!
 
+use crate::tree_view::TreeView;
 #[cfg(test)]
-use crate::binary_tree::Error;
-use crate::binary_tree::{BinaryTree, NodePtr};
+use crate::{
+    arena_tree::ArenaTree,
+    binary_tree::{BinaryTree, Error},
+};
 
 /// Primary Implementation
 ///
@@ -30,17 +33,22 @@ use crate::binary_tree::{BinaryTree, NodePtr};
 /// Does `paths_with_sum(tree, 21)` equal one or zero?
 /// In this case, zero. In the hashmap solution, one.
 ///
-pub fn paths_with_sum(tree: &BinaryTree<isize>, sum: isize) -> usize {
+pub fn paths_with_sum<V: TreeView<Data = isize> + ?Sized>(tree: &V, sum: isize) -> usize {
     let paths = vec![];
-    helper(&tree.head, &paths, sum)
+    helper(tree, tree.root(), &paths, sum)
 }
 /// Recursive helper
-fn helper(node: &Option<NodePtr<isize>>, paths: &[Vec<isize>], sum: isize) -> usize {
+fn helper<V: TreeView<Data = isize> + ?Sized>(
+    tree: &V,
+    node: Option<V::Node>,
+    paths: &[Vec<isize>],
+    sum: isize,
+) -> usize {
     let node = match node {
-        Some(n) => n,     // Unwrap the node-pointer
-        None => return 0, // Base case: null node-pointer
+        Some(n) => n,     // Unwrap the node-handle
+        None => return 0, // Base case: null node
     };
-    let node_data = node.borrow().data;
+    let node_data = *tree.data(&node);
 
     // Count up matches ending at this node
     let my_matches = paths
@@ -65,8 +73,8 @@ fn helper(node: &Option<NodePtr<isize>>, paths: &[Vec<isize>], sum: isize) -> us
 
     // Total up this node's matches with those from each child
     my_matches
-        + helper(&node.borrow().left, &child_paths, sum)
-        + helper(&node.borrow().right, &child_paths, sum)
+        + helper(tree, tree.left(&node), &child_paths, sum)
+        + helper(tree, tree.right(&node), &child_paths, sum)
 }
 #[test]
 fn test_paths_with_sum() -> Result<(), Error> {
@@ -85,6 +93,7 @@ fn test_paths_with_sum() -> Result<(), Error> {
 
     assert_eq!(paths_with_sum(&t1, 8), 4); // FIXME: verify these values offline
     assert_eq!(paths_with_sum(&t1, 6), 2); // FIXME: verify these values offline
+    assert_eq!(paths_with_sum(&ArenaTree::from(&t1), 8), 4);
 
     // Test case from the comments above
     let mut t1 = BinaryTree::default();
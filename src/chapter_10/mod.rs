@@ -9,3 +9,4 @@ pub mod p02_group_anagrams;
 pub mod p03_search_in_rotated_array;
 pub mod p04_search_sorted_no_size_array;
 pub mod p05_sparse_search;
+pub mod p10_rank_from_stream;
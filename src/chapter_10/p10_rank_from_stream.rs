@@ -0,0 +1,69 @@
+//!
+//! # Rank from Stream: Imagine you are reading in a stream of integers.
+//! Periodically, you wish to be able to look up the rank of a number x (the number of values less than or equal to x).
+//! Implement the data structures and algorithms to support these operations.
+//! That is, implement the method track(int x), which is called when each number is generated,
+//! and the method getRankOfNumber(int x), which returns the number of values less than or equal to x (not including x itself).
+//!
+//! EXAMPLE
+//!
+//! Stream (in order of appearance): 5, 1, 4, 4, 5, 9, 7, 13, 3
+//!
+//! getRankOfNumber(1) = 0
+//! getRankOfNumber(3) = 1
+//! getRankOfNumber(4) = 3
+//!
+//! Hints: #301, #376, #392
+//!
+
+use crate::binary_search_tree::RedBlackMap;
+
+/// Primary Implementation
+///
+/// Track each value in an order-statistic tree, which keeps sub-tree sizes on each node.
+/// Duplicates are made distinct by pairing each value with its sequence number in the stream.
+/// Both tracking and ranking then run in O(log n) time, courtesy of the tree's red-black balancing.
+///
+pub struct RankTracker {
+    tree: RedBlackMap<(isize, usize), ()>,
+}
+impl RankTracker {
+    /// Create a new, empty [RankTracker]
+    pub fn new() -> Self {
+        Self {
+            tree: RedBlackMap::new_red_black(),
+        }
+    }
+    /// Track stream-value `x`
+    pub fn track(&mut self, x: isize) {
+        let seq = self.tree.len();
+        self.tree.insert((x, seq), ());
+    }
+    /// Get the number of tracked values less than or equal to `x`, not including `x` itself.
+    /// Returns `None` if `x` has not been tracked.
+    pub fn get_rank_of_number(&self, x: isize) -> Option<usize> {
+        if self.tree.count_range((x, 0)..=(x, usize::MAX)) == 0 {
+            return None;
+        }
+        Some(self.tree.count_range(..=(x, usize::MAX)) - 1)
+    }
+}
+impl Default for RankTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn test_rank_from_stream() {
+    let mut tracker = RankTracker::new();
+    for x in [5, 1, 4, 4, 5, 9, 7, 13, 3] {
+        tracker.track(x);
+    }
+    assert_eq!(tracker.get_rank_of_number(1), Some(0));
+    assert_eq!(tracker.get_rank_of_number(3), Some(1));
+    assert_eq!(tracker.get_rank_of_number(4), Some(3));
+    assert_eq!(tracker.get_rank_of_number(5), Some(5));
+    assert_eq!(tracker.get_rank_of_number(13), Some(8));
+    assert_eq!(tracker.get_rank_of_number(6), None);
+}
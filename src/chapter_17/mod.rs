@@ -9,9 +9,11 @@ pub mod p02_shuffle;
 pub mod p07_baby_names;
 pub mod p08_circus_tower;
 pub mod p09_kth_multiple;
+pub mod p14_smallest_k;
 pub mod p15_longest_word;
 pub mod p16_the_masseuse;
 pub mod p17_multi_search;
 pub mod p18_shortest_supersequence;
+pub mod p20_continuous_median;
 pub mod p21_volume_of_histogram;
 pub mod p22_word_transformer;
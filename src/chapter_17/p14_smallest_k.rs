@@ -0,0 +1,121 @@
+//!
+//! # Smallest K:
+//!
+//! Design an algorithm to find the smallest K numbers in an array.
+//!
+//! Hints: #470, #530, #552, #593, #625, #647, #661, #678
+//!
+
+use std::cmp::Reverse;
+
+// Local Imports
+use crate::heap::Heap;
+
+///
+/// # Streaming Top-K
+///
+/// Keeps the `k` largest items seen so far, in O(k) space.
+/// Built on a min-[Heap] of size at most `k`, whose top is the smallest retained item,
+/// and the first to be evicted when a larger one arrives.
+///
+/// To instead retain the `k` *smallest*, wrap items in [Reverse], as [smallest_k] does.
+///
+#[derive(Debug, Clone)]
+pub struct TopK<T> {
+    k: usize,
+    heap: Heap<T>,
+}
+impl<T: Ord> TopK<T> {
+    /// Create a new, empty [TopK] retaining at most `k` items
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            heap: Heap::new(),
+        }
+    }
+    /// Offer `t` for retention.
+    /// Returns whichever item is not retained: either the evicted smallest, or `t` itself.
+    /// Returns `None` if nothing needed to be evicted.
+    pub fn push(&mut self, t: T) -> Option<T> {
+        if self.heap.len() < self.k {
+            self.heap.push(t);
+            return None;
+        }
+        if self.k == 0 {
+            return Some(t);
+        }
+        Some(self.heap.push_pop(t))
+    }
+    /// Get the smallest retained item, i.e. the current admission threshold.
+    /// Returns `None` if empty.
+    pub fn threshold(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+    /// Get the maximum number of retained items
+    pub fn k(&self) -> usize {
+        self.k
+    }
+    /// Get the number of currently retained items
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+    /// Boolean indication of whether no items are retained
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    /// Iterate over retained items, in arbitrary order
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.heap.iter()
+    }
+    /// Convert into a [Vec] of the retained items, largest first
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut v = self.heap.into_sorted_vec();
+        v.reverse();
+        v
+    }
+}
+impl<T: Ord> Extend<T> for TopK<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.push(t);
+        }
+    }
+}
+
+/// Primary Implementation
+///
+/// Stream each element through a [TopK] of [Reverse]d items,
+/// which retains the `k` largest reversed, i.e. the `k` smallest, in O(n log k) time.
+/// Returns them in ascending order.
+///
+pub fn smallest_k(items: &[isize], k: usize) -> Vec<isize> {
+    let mut top = TopK::new(k);
+    top.extend(items.iter().map(|&i| Reverse(i)));
+    top.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(i)| i)
+        .collect()
+}
+
+#[test]
+fn test_smallest_k() {
+    let items = [1, 5, 2, 9, 1, 4, 8, 2, 2, 7, 0];
+    assert_eq!(smallest_k(&items, 0), Vec::<isize>::new());
+    assert_eq!(smallest_k(&items, 1), vec![0]);
+    assert_eq!(smallest_k(&items, 4), vec![0, 1, 1, 2]);
+    assert_eq!(smallest_k(&items, 6), vec![0, 1, 1, 2, 2, 2]);
+    assert_eq!(smallest_k(&items, 100).len(), items.len());
+}
+
+#[test]
+fn test_top_k() {
+    let mut top = TopK::new(3);
+    assert_eq!(top.push(5), None);
+    assert_eq!(top.push(1), None);
+    assert_eq!(top.push(9), None);
+    assert_eq!(top.threshold(), Some(&1));
+    assert_eq!(top.push(0), Some(0)); // Not retained
+    assert_eq!(top.push(7), Some(1)); // Evicts the smallest
+    assert_eq!(top.len(), 3);
+    assert_eq!(top.into_sorted_vec(), vec![9, 7, 5]);
+}
@@ -78,11 +78,11 @@ pub fn shortest_supersequence(long: &[isize], short: &[isize]) -> Option<Range>
 }
 
 /// # Range
-/// Min and max indices-pair
+/// Min and max indices-pair. Closed: includes both `min` and `max`.
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Range {
-    min: usize,
-    max: usize,
+    pub min: usize,
+    pub max: usize,
 }
 
 /// Entry in the min-heap.
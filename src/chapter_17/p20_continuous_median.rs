@@ -0,0 +1,150 @@
+//!
+//! # Continuous Median:
+//!
+//! Numbers are randomly generated and passed to a method.
+//! Write a program to find and maintain the median value as new values are generated.
+//!
+//! Hints: #519, #546, #575, #709
+//!
+
+use crate::heap::{Heap, MaxHeap};
+
+///
+/// # Running Median
+///
+/// Splits all items seen into two halves:
+/// * The lower half, in a max-heap, and
+/// * The upper half, in a min-heap.
+///
+/// The lower half is kept equal in size to the upper, or larger by one,
+/// so that the median is always found at the top of one or both heaps.
+/// Insertions run in O(log n) time, and median-queries in O(1).
+///
+/// Items must be [Ord], which excludes floating-point types such as `f64`.
+/// [RunningMedian::median] averages the middle items as `f64`, and so requires `Into<f64>`,
+/// which excludes types such as `i64`, `u64` and `usize`. For these, use [RunningMedian::median_by]
+/// with a suitable midpoint function, or [RunningMedian::median_low] and [RunningMedian::median_high].
+///
+#[derive(Debug, Clone)]
+pub struct RunningMedian<T> {
+    lower: MaxHeap<T>,
+    upper: Heap<T>,
+}
+impl<T: Ord> Default for RunningMedian<T> {
+    fn default() -> Self {
+        Self {
+            lower: MaxHeap::new_max(),
+            upper: Heap::new(),
+        }
+    }
+}
+impl<T: Ord> RunningMedian<T> {
+    /// Create a new, empty [RunningMedian]. Also available via [Default].
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Insert a new item
+    pub fn insert(&mut self, t: T) {
+        // Push into the appropriate half
+        match self.lower.peek() {
+            Some(top) if t > *top => self.upper.push(t),
+            _ => self.lower.push(t),
+        }
+        // And re-balance, if either half has grown too large
+        if self.lower.len() > self.upper.len() + 1 {
+            let top = self.lower.pop().unwrap();
+            self.upper.push(top);
+        } else if self.upper.len() > self.lower.len() {
+            let top = self.upper.pop().unwrap();
+            self.lower.push(top);
+        }
+    }
+    /// Get the lower median: the middle item if an odd number have been inserted,
+    /// or the smaller of the two middle items if even.
+    /// Returns `None` if empty.
+    pub fn median_low(&self) -> Option<&T> {
+        self.lower.peek()
+    }
+    /// Get the upper median: the middle item if an odd number have been inserted,
+    /// or the larger of the two middle items if even.
+    /// Returns `None` if empty.
+    pub fn median_high(&self) -> Option<&T> {
+        if self.lower.len() > self.upper.len() {
+            self.lower.peek()
+        } else {
+            self.upper.peek()
+        }
+    }
+    /// Get the median, combining the lower and upper medians with function `midpoint`.
+    /// The two are the same item if an odd number have been inserted.
+    /// Returns `None` if empty.
+    pub fn median_by<R>(&self, midpoint: impl FnOnce(&T, &T) -> R) -> Option<R> {
+        Some(midpoint(self.median_low()?, self.median_high()?))
+    }
+    /// Get the number of items inserted
+    pub fn len(&self) -> usize {
+        self.lower.len() + self.upper.len()
+    }
+    /// Boolean indication of whether no items have been inserted
+    pub fn is_empty(&self) -> bool {
+        self.lower.is_empty()
+    }
+}
+impl<T: Ord + Copy + Into<f64>> RunningMedian<T> {
+    /// Get the median, averaging the two middle items if an even number have been inserted.
+    /// Returns `None` if empty.
+    pub fn median(&self) -> Option<f64> {
+        self.median_by(|&low, &high| (low.into() + high.into()) / 2.0)
+    }
+}
+
+/// Primary Implementation
+///
+/// Insert each item of `stream` into a [RunningMedian],
+/// returning the median after each insertion.
+///
+pub fn continuous_median(stream: &[i32]) -> Vec<f64> {
+    let mut rm = RunningMedian::new();
+    stream
+        .iter()
+        .map(|&i| {
+            rm.insert(i);
+            rm.median().unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_continuous_median() {
+    let stream = [5, 15, 1, 3, 2, 8, 7, 9, 10, 6, 11, 4];
+    let medians = continuous_median(&stream);
+    assert_eq!(
+        medians,
+        vec![5.0, 10.0, 5.0, 4.0, 3.0, 4.0, 5.0, 6.0, 7.0, 6.5, 7.0, 6.5]
+    );
+}
+
+#[test]
+fn test_running_median() {
+    let mut rm = RunningMedian::new();
+    assert!(rm.is_empty());
+    assert_eq!(rm.median_low(), None);
+
+    for k in (0..100).rev() {
+        rm.insert(k);
+    }
+    assert_eq!(rm.len(), 100);
+    assert_eq!(rm.median_low(), Some(&49));
+    assert_eq!(rm.median_high(), Some(&50));
+    assert_eq!(rm.median(), Some(49.5));
+
+    rm.insert(1_000);
+    assert_eq!(rm.median_low(), Some(&50));
+    assert_eq!(rm.median_high(), Some(&50));
+
+    // Types without `Into<f64>` supply their own midpoint, here one which cannot overflow
+    let mut rm = RunningMedian::new();
+    rm.insert(u64::MAX);
+    rm.insert(u64::MAX - 2);
+    assert_eq!(rm.median_by(|a, b| a + (b - a) / 2), Some(u64::MAX - 1));
+}
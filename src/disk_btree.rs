@@ -0,0 +1,846 @@
+//!
+//! # Disk-Backed B-Tree
+//!
+//! An ordered map from byte-string keys to byte-string values, stored in a single local file,
+//! for datasets too large to hold in memory as a [crate::binary_search_tree::BstMap].
+//!
+//! Each tree node is serialized to one fixed-size page of the file. Nodes hold up to `order - 1`
+//! entries and `order` children, so that lookups touch O(log n / log order) pages.
+//! Recently used pages are held, decoded, in an LRU [PageCache], and written back when evicted or flushed.
+//! Page zero holds a header recording the tree's [Config], root page, and length.
+//!
+//! Insertion follows Cormen et al.: full nodes are split on the way down, so that each insert
+//! makes a single root-to-leaf pass. Removal is not supported.
+//!
+//! [DiskBTree::flush] writes every dirty page, then the header, and syncs the file's data to disk after each.
+//! The file is *not* crash-consistent between flushes: pages evicted from the cache are overwritten in place,
+//! so a crash or power loss after an eviction, but before the next flush completes, may leave a file
+//! whose pages mix old and new contents. Callers needing durability across crashes should
+//! flush at their own checkpoints, and keep a copy of the file from the last one.
+//!
+//! Uses only the standard library.
+//!
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    fs::{File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    ops::{Bound, RangeBounds},
+    path::Path,
+};
+
+/// Index of a page within the file
+pub type PageId = u64;
+
+// File identifier, at the start of the header page
+const MAGIC: &[u8; 8] = b"CTCIBTR1";
+// Header length, in bytes: magic, four u32 config fields, and three u64 fields
+const HEADER_LEN: usize = 8 + 4 * 4 + 3 * 8;
+// Per-node encoding overhead: leaf flag and entry count
+const NODE_HEADER_LEN: usize = 1 + 2;
+// Per-entry encoding overhead: key and value lengths
+const ENTRY_HEADER_LEN: usize = 2 + 4;
+// Largest order whose full nodes' entry counts fit the node header's u16
+const MAX_ORDER: usize = u16::MAX as usize + 1;
+
+///
+/// # B-Tree Configuration
+///
+/// Fixed when a tree is created, and recorded in its file's header.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// Page size, in bytes
+    pub page_size: usize,
+    /// Maximum number of children per node, or `None` for as many as fit in a page
+    pub order: Option<usize>,
+    /// Maximum key length, in bytes
+    pub max_key_len: usize,
+    /// Maximum value length, in bytes
+    pub max_value_len: usize,
+}
+impl Default for Config {
+    /// 4 KiB pages, 32-byte keys, and 64-byte values
+    fn default() -> Self {
+        Self {
+            page_size: 4096,
+            order: None,
+            max_key_len: 32,
+            max_value_len: 64,
+        }
+    }
+}
+impl Config {
+    /// Set the page size, in bytes
+    pub fn page_size(mut self, bytes: usize) -> Self {
+        self.page_size = bytes;
+        self
+    }
+    /// Set the maximum number of children per node
+    pub fn order(mut self, order: usize) -> Self {
+        self.order = Some(order);
+        self
+    }
+    /// Set the maximum key length, in bytes
+    pub fn max_key_len(mut self, bytes: usize) -> Self {
+        self.max_key_len = bytes;
+        self
+    }
+    /// Set the maximum value length, in bytes
+    pub fn max_value_len(mut self, bytes: usize) -> Self {
+        self.max_value_len = bytes;
+        self
+    }
+    /// Get the largest order whose full nodes fit in a page, and whose entry counts fit a node's header
+    pub fn max_order(&self) -> usize {
+        // A full node of order `m` has `m - 1` entries and `m` eight-byte child-ids
+        let entry = ENTRY_HEADER_LEN
+            .saturating_add(self.max_key_len)
+            .saturating_add(self.max_value_len);
+        let fit = self
+            .page_size
+            .saturating_sub(NODE_HEADER_LEN)
+            .saturating_add(entry)
+            / entry.saturating_add(8);
+        fit.min(MAX_ORDER)
+    }
+    /// Get the effective order, resolving `None` to [Config::max_order]
+    fn resolved_order(&self) -> usize {
+        self.order.unwrap_or_else(|| self.max_order())
+    }
+    /// Check that full nodes fit in a page, and that splitting a full node leaves two non-empty halves
+    fn validate(&self) -> io::Result<()> {
+        if self.max_key_len > u16::MAX as usize || self.max_value_len > u32::MAX as usize {
+            return Err(invalid_input("B-tree key or value length limit too large"));
+        }
+        let order = self.resolved_order();
+        if self.page_size < HEADER_LEN || order < 4 || order > self.max_order() {
+            return Err(invalid_input(
+                "B-tree order must be at least 4, and fit in a page",
+            ));
+        }
+        Ok(())
+    }
+}
+
+///
+/// # Tree Node
+///
+/// Entries are sorted by key. Internal nodes have one more child than entries;
+/// the sub-tree at `children[i]` holds keys between `keys[i - 1]` and `keys[i]`.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Node {
+    leaf: bool,
+    keys: Vec<Vec<u8>>,
+    values: Vec<Vec<u8>>,
+    children: Vec<PageId>,
+}
+impl Node {
+    /// Create a new, empty leaf
+    fn leaf() -> Self {
+        Self {
+            leaf: true,
+            ..Default::default()
+        }
+    }
+    /// Boolean indication of whether the node has the maximum number of entries for order `order`
+    fn is_full(&self, order: usize) -> bool {
+        self.keys.len() >= order - 1
+    }
+    /// Serialize to a zero-padded page of `page_size` bytes
+    fn encode(&self, page_size: usize) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(page_size);
+        buf.push(self.leaf as u8);
+        buf.extend_from_slice(&(self.keys.len() as u16).to_le_bytes());
+        for (key, value) in self.keys.iter().zip(self.values.iter()) {
+            buf.extend_from_slice(&(key.len() as u16).to_le_bytes());
+            buf.extend_from_slice(key);
+            buf.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            buf.extend_from_slice(value);
+        }
+        for child in self.children.iter() {
+            buf.extend_from_slice(&child.to_le_bytes());
+        }
+        buf.resize(page_size, 0);
+        buf
+    }
+    /// Deserialize from page-contents `page`
+    fn decode(page: &[u8]) -> io::Result<Self> {
+        let mut reader = PageReader { page, pos: 0 };
+        let leaf = match reader.take(1)?[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(invalid_data("corrupt B-tree node")),
+        };
+        let count = u16::from_le_bytes(reader.array()?) as usize;
+        let mut node = Node {
+            leaf,
+            keys: Vec::with_capacity(count),
+            values: Vec::with_capacity(count),
+            children: Vec::new(),
+        };
+        for _ in 0..count {
+            let len = u16::from_le_bytes(reader.array()?) as usize;
+            node.keys.push(reader.take(len)?.to_vec());
+            let len = u32::from_le_bytes(reader.array()?) as usize;
+            node.values.push(reader.take(len)?.to_vec());
+        }
+        if !leaf {
+            for _ in 0..=count {
+                node.children.push(u64::from_le_bytes(reader.array()?));
+            }
+        }
+        Ok(node)
+    }
+}
+
+/// Bounds-checked cursor over the bytes of a page
+struct PageReader<'p> {
+    page: &'p [u8],
+    pos: usize,
+}
+impl<'p> PageReader<'p> {
+    /// Take the next `len` bytes
+    fn take(&mut self, len: usize) -> io::Result<&'p [u8]> {
+        let end = self.pos + len;
+        let bytes = self
+            .page
+            .get(self.pos..end)
+            .ok_or_else(|| invalid_data("B-tree node overruns its page"))?;
+        self.pos = end;
+        Ok(bytes)
+    }
+    /// Take the next `N` bytes, as an array
+    fn array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let mut arr = [0; N];
+        arr.copy_from_slice(self.take(N)?);
+        Ok(arr)
+    }
+}
+
+/// Page Cache Statistics
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Page requests served from the cache
+    pub hits: u64,
+    /// Page requests read from the file
+    pub misses: u64,
+    /// Pages written to the file
+    pub writes: u64,
+}
+
+///
+/// # Page Cache
+///
+/// Holds up to `capacity` decoded nodes, evicting the least-recently used.
+/// Modified nodes are marked dirty, and written back when evicted, or on [DiskBTree::flush].
+///
+#[derive(Debug)]
+pub struct PageCache {
+    capacity: usize,
+    entries: HashMap<PageId, Cached>,
+    /// Pages in order of last use, keyed by a use-counter
+    lru: BTreeMap<u64, PageId>,
+    clock: u64,
+    stats: CacheStats,
+}
+/// Cached node, with its dirty-flag and last-use time
+#[derive(Debug)]
+struct Cached {
+    node: Node,
+    dirty: bool,
+    used: u64,
+}
+impl PageCache {
+    /// Create a new, empty cache of `capacity` pages. Holds at least one page.
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            lru: BTreeMap::new(),
+            clock: 0,
+            stats: CacheStats::default(),
+        }
+    }
+    /// Mark `id` as most-recently used
+    fn touch(&mut self, id: PageId) {
+        let entry = self.entries.get_mut(&id).unwrap();
+        self.lru.remove(&entry.used);
+        self.clock += 1;
+        entry.used = self.clock;
+        self.lru.insert(self.clock, id);
+    }
+    /// Insert `node` at `id`, with dirty-flag `dirty`. Returns any evicted dirty page, for write-back.
+    fn insert(&mut self, id: PageId, node: Node, dirty: bool) -> Option<(PageId, Node)> {
+        let mut evicted = None;
+        if !self.entries.contains_key(&id) && self.entries.len() >= self.capacity {
+            let (_, victim) = self.lru.pop_first().unwrap();
+            let cached = self.entries.remove(&victim).unwrap();
+            if cached.dirty {
+                evicted = Some((victim, cached.node));
+            }
+        }
+        let used = self.entries.get(&id).map_or(0, |c| c.used);
+        let dirty = dirty || self.entries.get(&id).is_some_and(|c| c.dirty);
+        self.entries.insert(id, Cached { node, dirty, used });
+        self.touch(id);
+        evicted
+    }
+}
+
+///
+/// # Disk-Backed B-Tree
+///
+/// Changes are buffered in the [PageCache], and written to the file by [DiskBTree::flush].
+/// Dropping the tree flushes it, ignoring any errors; call [DiskBTree::flush] to observe them.
+///
+#[derive(Debug)]
+pub struct DiskBTree {
+    file: File,
+    config: Config,
+    order: usize,
+    root: PageId,
+    pages: u64,
+    len: u64,
+    cache: PageCache,
+}
+impl DiskBTree {
+    /// Create a new, empty tree in file `path`, replacing any existing file,
+    /// caching up to `cache_pages` pages in memory
+    pub fn create(path: impl AsRef<Path>, config: Config, cache_pages: usize) -> io::Result<Self> {
+        config.validate()?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        let mut tree = Self {
+            file,
+            config,
+            order: config.resolved_order(),
+            root: 1,
+            pages: 2,
+            len: 0,
+            cache: PageCache::new(cache_pages),
+        };
+        tree.write(1, Node::leaf())?;
+        tree.flush()?;
+        Ok(tree)
+    }
+    /// Open an existing tree in file `path`, caching up to `cache_pages` pages in memory
+    pub fn open(path: impl AsRef<Path>, cache_pages: usize) -> io::Result<Self> {
+        let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+        let mut header = [0; HEADER_LEN];
+        file.read_exact(&mut header)?;
+        let mut reader = PageReader {
+            page: &header,
+            pos: 0,
+        };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(invalid_data("not a B-tree file"));
+        }
+        let mut next_u32 =
+            || -> io::Result<usize> { Ok(u32::from_le_bytes(reader.array()?) as usize) };
+        let config = Config {
+            page_size: next_u32()?,
+            order: Some(next_u32()?),
+            max_key_len: next_u32()?,
+            max_value_len: next_u32()?,
+        };
+        config
+            .validate()
+            .map_err(|_| invalid_data("corrupt B-tree header"))?;
+        let root = u64::from_le_bytes(reader.array()?);
+        let pages = u64::from_le_bytes(reader.array()?);
+        let len = u64::from_le_bytes(reader.array()?);
+        Ok(Self {
+            file,
+            order: config.resolved_order(),
+            config,
+            root,
+            pages,
+            len,
+            cache: PageCache::new(cache_pages),
+        })
+    }
+    /// Get the tree's configuration, with its order resolved
+    pub fn config(&self) -> Config {
+        Config {
+            order: Some(self.order),
+            ..self.config
+        }
+    }
+    /// Get the number of entries
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+    /// Boolean indication of whether the tree is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Get the number of pages in the file, including the header
+    pub fn pages(&self) -> u64 {
+        self.pages
+    }
+    /// Get the page-cache statistics
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats
+    }
+    /// Get a copy of the value at `key`, if present
+    pub fn get(&mut self, key: &[u8]) -> io::Result<Option<Vec<u8>>> {
+        let mut id = self.root;
+        loop {
+            let node = self.read(id)?;
+            match node.keys.binary_search_by(|k| k.as_slice().cmp(key)) {
+                Ok(i) => return Ok(Some(node.values[i].clone())),
+                Err(_) if node.leaf => return Ok(None),
+                Err(i) => id = node.children[i],
+            }
+        }
+    }
+    /// Boolean indication of whether `key` is present
+    pub fn contains_key(&mut self, key: &[u8]) -> io::Result<bool> {
+        Ok(self.get(key)?.is_some())
+    }
+    /// Insert `value` at `key`.
+    /// Returns the previous value at `key`, if one was present.
+    /// Keys and values longer than the [Config] limits are an [io::ErrorKind::InvalidInput] error.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> io::Result<Option<Vec<u8>>> {
+        if key.len() > self.config.max_key_len || value.len() > self.config.max_value_len {
+            return Err(invalid_input("B-tree key or value too long"));
+        }
+        // Split a full root ahead of time, growing the tree by a level
+        let order = self.order;
+        if self.read(self.root)?.is_full(order) {
+            let old_root = self.root;
+            let mut root = Node {
+                leaf: false,
+                children: vec![old_root],
+                ..Default::default()
+            };
+            self.root = self.allocate();
+            self.split_child(&mut root, 0)?;
+            self.write(self.root, root)?;
+        }
+        // Walk down, splitting any full child before descending into it
+        let mut id = self.root;
+        loop {
+            let mut node = self.read(id)?.clone();
+            let mut i = match node.keys.binary_search_by(|k| k.as_slice().cmp(key)) {
+                Ok(i) => {
+                    let old = std::mem::replace(&mut node.values[i], value.to_vec());
+                    self.write(id, node)?;
+                    return Ok(Some(old));
+                }
+                Err(i) => i,
+            };
+            if node.leaf {
+                node.keys.insert(i, key.to_vec());
+                node.values.insert(i, value.to_vec());
+                self.write(id, node)?;
+                self.len += 1;
+                return Ok(None);
+            }
+            if self.read(node.children[i])?.is_full(order) {
+                self.split_child(&mut node, i)?;
+                self.write(id, node.clone())?;
+                // The child's median now sits at `i`, and may be our key, or precede it
+                match node.keys[i].as_slice().cmp(key) {
+                    Ordering::Equal => continue, // Replace it on the next pass
+                    Ordering::Less => i += 1,
+                    Ordering::Greater => (),
+                }
+            }
+            id = node.children[i];
+        }
+    }
+    /// Get an iterator over the entries with keys in `range`, in ascending key-order.
+    /// Entries are read lazily, through the page cache.
+    pub fn range<Q, R>(&mut self, range: R) -> Range<'_>
+    where
+        Q: AsRef<[u8]> + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let own = |b: Bound<&Q>| match b {
+            Bound::Included(k) => Bound::Included(k.as_ref().to_vec()),
+            Bound::Excluded(k) => Bound::Excluded(k.as_ref().to_vec()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let start = own(range.start_bound());
+        let end = own(range.end_bound());
+        let root = self.root;
+        let mut scan = Range {
+            tree: self,
+            stack: Vec::new(),
+            end,
+            error: None,
+            done: false,
+        };
+        // Descend to the first entry in range. Any failure is reported by the first call to `next`.
+        if let Err(e) = scan.descend(root, &start) {
+            scan.error = Some(e);
+        }
+        scan
+    }
+    /// Get an iterator over all entries, in ascending key-order
+    pub fn iter(&mut self) -> Range<'_> {
+        self.range::<[u8], _>(..)
+    }
+    /// Write all dirty cached pages, and the header, to the file, and sync its data to disk.
+    /// Pages are synced before the header is written, so the header never refers to unwritten pages.
+    pub fn flush(&mut self) -> io::Result<()> {
+        let mut dirty: Vec<PageId> = self
+            .cache
+            .entries
+            .iter()
+            .filter(|(_, c)| c.dirty)
+            .map(|(&id, _)| id)
+            .collect();
+        dirty.sort_unstable();
+        for id in dirty {
+            let page = self.cache.entries[&id].node.encode(self.config.page_size);
+            self.write_page(id, &page)?;
+            self.cache.entries.get_mut(&id).unwrap().dirty = false;
+        }
+        self.file.sync_data()?;
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend_from_slice(MAGIC);
+        for field in [
+            self.config.page_size,
+            self.order,
+            self.config.max_key_len,
+            self.config.max_value_len,
+        ] {
+            header.extend_from_slice(&(field as u32).to_le_bytes());
+        }
+        for field in [self.root, self.pages, self.len] {
+            header.extend_from_slice(&field.to_le_bytes());
+        }
+        header.resize(self.config.page_size, 0);
+        self.write_page(0, &header)?;
+        self.file.sync_data()
+    }
+    /// Check the tree's ordering, node sizes, and that all leaves share a depth.
+    /// Reads every page.
+    pub fn check_invariants(&mut self) -> io::Result<bool> {
+        let mut leaf_depth = None;
+        let count = self.check_helper(self.root, None, None, 0, &mut leaf_depth)?;
+        Ok(count == Some(self.len))
+    }
+    /// Recursive helper for `check_invariants`.
+    /// Returns the number of entries in sub-tree `id` if valid, with all keys between `min` and `max`.
+    fn check_helper(
+        &mut self,
+        id: PageId,
+        min: Option<&[u8]>,
+        max: Option<&[u8]>,
+        depth: usize,
+        leaf_depth: &mut Option<usize>,
+    ) -> io::Result<Option<u64>> {
+        let node = self.read(id)?.clone();
+        let sorted = node.keys.windows(2).all(|w| w[0] < w[1]);
+        let bounded = node
+            .keys
+            .first()
+            .map_or(true, |k| min.map_or(true, |m| m < k.as_slice()))
+            && node
+                .keys
+                .last()
+                .map_or(true, |k| max.map_or(true, |m| k.as_slice() < m));
+        // Non-root nodes keep at least the smaller half of a split full node
+        let min_keys = if id == self.root {
+            0
+        } else {
+            (self.order - 2) / 2
+        };
+        let sized = node.keys.len() >= min_keys && node.keys.len() < self.order;
+        if !sorted || !bounded || !sized {
+            return Ok(None);
+        }
+        if node.leaf {
+            let same_depth = *leaf_depth.get_or_insert(depth) == depth;
+            return Ok(same_depth.then_some(node.keys.len() as u64));
+        }
+        let mut count = node.keys.len() as u64;
+        for (i, &child) in node.children.iter().enumerate() {
+            let lo = if i == 0 {
+                min
+            } else {
+                Some(node.keys[i - 1].as_slice())
+            };
+            let hi = node.keys.get(i).map(Vec::as_slice).or(max);
+            match self.check_helper(child, lo, hi, depth + 1, leaf_depth)? {
+                Some(c) => count += c,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(count))
+    }
+    /// Split the full child at `parent.children[i]` in two, lifting its median entry into `parent`.
+    /// Writes both halves. Writing `parent` is left to the caller.
+    fn split_child(&mut self, parent: &mut Node, i: usize) -> io::Result<()> {
+        let left_id = parent.children[i];
+        let mut left = self.read(left_id)?.clone();
+        let mid = left.keys.len() / 2;
+        let right = Node {
+            leaf: left.leaf,
+            keys: left.keys.split_off(mid + 1),
+            values: left.values.split_off(mid + 1),
+            children: if left.leaf {
+                Vec::new()
+            } else {
+                left.children.split_off(mid + 1)
+            },
+        };
+        parent.keys.insert(i, left.keys.pop().unwrap());
+        parent.values.insert(i, left.values.pop().unwrap());
+        let right_id = self.allocate();
+        parent.children.insert(i + 1, right_id);
+        self.write(left_id, left)?;
+        self.write(right_id, right)
+    }
+    /// Allocate a new page at the end of the file
+    fn allocate(&mut self) -> PageId {
+        self.pages += 1;
+        self.pages - 1
+    }
+    /// Get the node at page `id`, reading it into the cache if necessary
+    fn read(&mut self, id: PageId) -> io::Result<&Node> {
+        if self.cache.entries.contains_key(&id) {
+            self.cache.stats.hits += 1;
+            self.cache.touch(id);
+        } else {
+            self.cache.stats.misses += 1;
+            let mut page = vec![0; self.config.page_size];
+            self.file
+                .seek(SeekFrom::Start(id * self.config.page_size as u64))?;
+            self.file.read_exact(&mut page)?;
+            let node = Node::decode(&page)?;
+            if let Some((victim, evicted)) = self.cache.insert(id, node, false) {
+                self.write_page(victim, &evicted.encode(self.config.page_size))?;
+            }
+        }
+        Ok(&self.cache.entries[&id].node)
+    }
+    /// Replace the node at page `id` with `node`, in the cache
+    fn write(&mut self, id: PageId, node: Node) -> io::Result<()> {
+        if let Some((victim, evicted)) = self.cache.insert(id, node, true) {
+            self.write_page(victim, &evicted.encode(self.config.page_size))?;
+        }
+        Ok(())
+    }
+    /// Write `page` to the file at page `id`
+    fn write_page(&mut self, id: PageId, page: &[u8]) -> io::Result<()> {
+        self.cache.stats.writes += 1;
+        self.file
+            .seek(SeekFrom::Start(id * self.config.page_size as u64))?;
+        self.file.write_all(page)
+    }
+}
+impl Drop for DiskBTree {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+///
+/// # Range Scan
+///
+/// In-order iterator over a [DiskBTree], holding a stack of (page, next-entry) positions,
+/// one per level. Yields `Err` at most once, upon any failure to read a page, and then ends.
+///
+#[derive(Debug)]
+pub struct Range<'t> {
+    tree: &'t mut DiskBTree,
+    stack: Vec<(PageId, usize)>,
+    end: Bound<Vec<u8>>,
+    /// Failure during construction, pending report
+    error: Option<io::Error>,
+    done: bool,
+}
+impl<'t> Range<'t> {
+    /// Descend from page `id` to the first entry at or after `start`, stacking the position at each level
+    fn descend(&mut self, mut id: PageId, start: &Bound<Vec<u8>>) -> io::Result<()> {
+        loop {
+            let node = self.tree.read(id)?;
+            let i = node.keys.partition_point(|k| match start {
+                Bound::Unbounded => false,
+                Bound::Included(s) => k < s,
+                Bound::Excluded(s) => k <= s,
+            });
+            let child = node.children.get(i).copied();
+            self.stack.push((id, i));
+            match child {
+                Some(c) if !node.leaf => id = c,
+                _ => return Ok(()),
+            }
+        }
+    }
+    /// Get the next entry, if any, advancing the stack
+    fn advance(&mut self) -> io::Result<Option<(Vec<u8>, Vec<u8>)>> {
+        while let Some(&(id, i)) = self.stack.last() {
+            let node = self.tree.read(id)?;
+            if i >= node.keys.len() {
+                self.stack.pop();
+                continue;
+            }
+            let entry = (node.keys[i].clone(), node.values[i].clone());
+            let next_child = (!node.leaf).then(|| node.children[i + 1]);
+            self.stack.last_mut().unwrap().1 += 1;
+            if let Some(child) = next_child {
+                self.descend(child, &Bound::Unbounded)?;
+            }
+            return Ok(Some(entry));
+        }
+        Ok(None)
+    }
+}
+impl<'t> Iterator for Range<'t> {
+    type Item = io::Result<(Vec<u8>, Vec<u8>)>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if let Some(err) = self.error.take() {
+            self.done = true;
+            return Some(Err(err));
+        }
+        let result = self.advance();
+        let in_range = match &result {
+            Ok(Some((key, _))) => match &self.end {
+                Bound::Unbounded => true,
+                Bound::Included(e) => key <= e,
+                Bound::Excluded(e) => key < e,
+            },
+            Ok(None) => false,
+            Err(_) => true,
+        };
+        if !in_range || result.is_err() {
+            self.done = true;
+        }
+        if !in_range {
+            return None;
+        }
+        result.transpose()
+    }
+}
+
+/// Create an [io::ErrorKind::InvalidInput] error with message `msg`
+fn invalid_input(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+/// Create an [io::ErrorKind::InvalidData] error with message `msg`
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+/// Create a path for test-file `name` in the system temporary directory
+fn test_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("ctci-test-{}-{}.btree", name, std::process::id()))
+}
+
+#[test]
+fn test_disk_btree() -> io::Result<()> {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    // Small pages and a tiny cache, to force a deep tree and plenty of eviction
+    let path = test_path("disk-btree");
+    let config = Config::default()
+        .page_size(256)
+        .max_key_len(8)
+        .max_value_len(8);
+    let mut reference = BTreeMap::new();
+    {
+        let mut tree = DiskBTree::create(&path, config, 4)?;
+        assert_eq!(tree.config().order, Some(config.max_order()));
+        let mut rng = StdRng::seed_from_u64(46);
+        for _ in 0..3000 {
+            let key = format!("{:06}", rng.gen_range(0..5000)).into_bytes();
+            let value = rng.gen::<u32>().to_le_bytes().to_vec();
+            assert_eq!(tree.insert(&key, &value)?, reference.insert(key, value));
+        }
+        assert_eq!(tree.len(), reference.len() as u64);
+        assert!(tree.check_invariants()?);
+        assert!(tree.cache_stats().writes > 0);
+    }
+    // Re-open, with a cold cache, and compare against the reference
+    let mut tree = DiskBTree::open(&path, 16)?;
+    assert_eq!(tree.len(), reference.len() as u64);
+    assert!(tree.check_invariants()?);
+    for (key, value) in reference.iter() {
+        assert_eq!(tree.get(key)?.as_ref(), Some(value));
+    }
+    assert_eq!(tree.get(b"nope")?, None);
+    assert!(!tree.contains_key(b"004999x")?);
+
+    let all: Vec<_> = tree.iter().collect::<io::Result<_>>()?;
+    assert!(all.iter().map(|(k, v)| (k, v)).eq(reference.iter()));
+
+    let scan: Vec<_> = tree.range("001000".."002000").collect::<io::Result<_>>()?;
+    let expected: Vec<_> = reference
+        .range(b"001000".to_vec()..b"002000".to_vec())
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    assert_eq!(scan, expected);
+
+    let scan: Vec<_> = tree.range("004990"..).map(|r| r.unwrap().0).collect();
+    assert!(scan.iter().all(|k| k.as_slice() >= b"004990".as_slice()));
+    assert_eq!(scan.len(), reference.range(b"004990".to_vec()..).count());
+    assert_eq!(tree.range("zzz"..).count(), 0);
+
+    drop(tree);
+    std::fs::remove_file(&path)
+}
+
+#[test]
+fn test_disk_btree_cache_and_errors() -> io::Result<()> {
+    let path = test_path("disk-btree-errors");
+
+    // Orders too small, or too large for a page, are rejected
+    let config = Config::default().page_size(512);
+    assert!(DiskBTree::create(&path, config.order(3), 8).is_err());
+    let err = DiskBTree::create(&path, config.order(config.max_order() + 1), 8).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    // As are orders whose entry counts overflow a node's header, however large the page
+    let big = Config::default()
+        .page_size(4 << 20)
+        .max_key_len(8)
+        .max_value_len(8);
+    assert_eq!(big.max_order(), u16::MAX as usize + 1);
+    let err = DiskBTree::create(&path, big.order(u16::MAX as usize + 2), 8).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    // And over-long key and value limits, without overflowing
+    let err = DiskBTree::create(&path, config.max_value_len(usize::MAX), 8).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let mut tree = DiskBTree::create(&path, config.order(4), 8)?;
+    let err = tree.insert(&[0; 33], b"").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    for k in 0..100u32 {
+        tree.insert(&k.to_be_bytes(), b"v")?;
+    }
+    assert!(tree.check_invariants()?);
+
+    // Repeated lookups of the same key are served from the cache
+    tree.get(&7u32.to_be_bytes())?;
+    let before = tree.cache_stats();
+    for _ in 0..10 {
+        tree.get(&7u32.to_be_bytes())?;
+    }
+    let after = tree.cache_stats();
+    assert_eq!(after.misses, before.misses);
+    assert!(after.hits > before.hits);
+    drop(tree);
+
+    // Files other than B-trees fail to open
+    std::fs::write(&path, vec![0u8; 4096])?;
+    let err = DiskBTree::open(&path, 8).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    std::fs::remove_file(&path)
+}
@@ -0,0 +1,431 @@
+//!
+//! # External Merge Sort
+//!
+//! Sorts record-streams too large to fit in memory.
+//!
+//! Input is read in chunks of bounded (approximate) memory footprint.
+//! Each chunk is sorted in memory by [MergeSort], and written to a temporary "run" file.
+//! Runs are then combined by a k-way merge through a [Heap] holding the head of each run,
+//! in as many passes as required to keep the number of simultaneously open runs under `fan_in`.
+//!
+//! Records are read and written by a [Codec], covering e.g. newline-delimited text ([LineCodec])
+//! and fixed-width binary ([FixedWidthCodec]).
+//!
+
+use std::{
+    cmp::Ordering,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering as AtomicOrdering},
+};
+
+// Local Imports
+use crate::heap::Heap;
+use crate::sort::{MergeSort, Sorter};
+
+///
+/// # Record Codec
+///
+/// Reads and writes records of type `Record` from and to byte-streams.
+///
+pub trait Codec {
+    /// Record Type
+    type Record;
+    /// Read the next record from `reader`. Returns `Ok(None)` at end-of-stream.
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Self::Record>>;
+    /// Write `record` to `writer`
+    fn write<W: Write>(&self, writer: &mut W, record: &Self::Record) -> io::Result<()>;
+    /// Approximate in-memory size of `record`, in bytes, counted against the memory budget
+    fn size(&self, record: &Self::Record) -> usize;
+}
+
+///
+/// # Newline-Delimited Text Codec
+///
+/// Each record is a line of UTF-8 text, stored without its trailing newline (`\n` or `\r\n`).
+/// Every record is written with a trailing `\n`.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LineCodec;
+impl Codec for LineCodec {
+    type Record = String;
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if line.ends_with('\n') {
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+        Ok(Some(line))
+    }
+    fn write<W: Write>(&self, writer: &mut W, record: &String) -> io::Result<()> {
+        writer.write_all(record.as_bytes())?;
+        writer.write_all(b"\n")
+    }
+    fn size(&self, record: &String) -> usize {
+        std::mem::size_of::<String>() + record.capacity()
+    }
+}
+
+///
+/// # Fixed-Width Binary Codec
+///
+/// Each record is exactly `width` bytes, which must be non-zero.
+/// A trailing partial record is an [io::ErrorKind::UnexpectedEof] error.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedWidthCodec {
+    pub width: usize,
+}
+impl FixedWidthCodec {
+    /// Create a new [FixedWidthCodec] of `width` bytes per record.
+    /// Panics if `width` is zero.
+    pub fn new(width: usize) -> Self {
+        assert!(width > 0, "FixedWidthCodec width must be non-zero");
+        Self { width }
+    }
+}
+impl Codec for FixedWidthCodec {
+    type Record = Vec<u8>;
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Vec<u8>>> {
+        if self.width == 0 {
+            // Zero-width records would never consume any input
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "codec width must be non-zero",
+            ));
+        }
+        if reader.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+        let mut record = vec![0; self.width];
+        reader.read_exact(&mut record)?;
+        Ok(Some(record))
+    }
+    fn write<W: Write>(&self, writer: &mut W, record: &Vec<u8>) -> io::Result<()> {
+        if record.len() != self.width {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "record length does not match codec width",
+            ));
+        }
+        writer.write_all(record)
+    }
+    fn size(&self, record: &Vec<u8>) -> usize {
+        std::mem::size_of::<Vec<u8>>() + record.capacity()
+    }
+}
+
+///
+/// # External Sort
+///
+/// Configuration and entry-points for sorting streams of records decoded by codec `C`.
+///
+#[derive(Debug, Clone)]
+pub struct ExternalSort<C> {
+    /// Record codec
+    pub codec: C,
+    /// Approximate memory budget for each in-memory chunk, in bytes
+    pub memory: usize,
+    /// Maximum number of runs merged at once
+    pub fan_in: usize,
+    /// Directory for temporary run files
+    pub temp_dir: PathBuf,
+}
+impl<C: Codec> ExternalSort<C> {
+    /// Create a new [ExternalSort] with a 64 MiB memory budget,
+    /// and run files in the system temporary directory.
+    pub fn new(codec: C) -> Self {
+        Self {
+            codec,
+            memory: 64 * 1024 * 1024,
+            fan_in: 64,
+            temp_dir: std::env::temp_dir(),
+        }
+    }
+    /// Set the memory budget, in bytes
+    pub fn memory(mut self, bytes: usize) -> Self {
+        self.memory = bytes;
+        self
+    }
+    /// Set the maximum number of runs merged at once. Must be at least two.
+    pub fn fan_in(mut self, fan_in: usize) -> Self {
+        self.fan_in = fan_in.max(2);
+        self
+    }
+    /// Set the directory for temporary run files
+    pub fn temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = dir.into();
+        self
+    }
+    /// Sort the records of file `input` in ascending order, writing them to file `output`.
+    ///
+    /// Writes to a temporary file in `output`'s directory, which is renamed into place once complete.
+    /// `output` may therefore be the same file as `input`, and is left untouched if sorting fails.
+    pub fn sort_file(&self, input: impl AsRef<Path>, output: impl AsRef<Path>) -> io::Result<()>
+    where
+        C::Record: Ord,
+    {
+        let output = output.as_ref();
+        let dir = match output.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let temp = Run::create(dir)?;
+        let reader = File::open(input)?;
+        let mut writer = BufWriter::new(File::create(&temp.path)?);
+        self.sort(reader, &mut writer)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&temp.path, output)
+    }
+    /// Sort the records of `input` in ascending order, writing them to `output`
+    pub fn sort<R: Read, W: Write>(&self, input: R, output: W) -> io::Result<()>
+    where
+        C::Record: Ord,
+    {
+        self.sort_by(input, output, C::Record::cmp)
+    }
+    /// Sort the records of `input` with comparator-function `cmp`, writing them to `output`.
+    /// Stable: equal records retain their relative order.
+    pub fn sort_by<R, W, F>(&self, input: R, mut output: W, cmp: F) -> io::Result<()>
+    where
+        R: Read,
+        W: Write,
+        F: Fn(&C::Record, &C::Record) -> Ordering,
+    {
+        let mut reader = BufReader::new(input);
+        let mut runs = Vec::new();
+        loop {
+            let (mut chunk, done) = self.read_chunk(&mut reader)?;
+            MergeSort::sort_by(&mut chunk, &cmp);
+            if done && runs.is_empty() {
+                // Everything fit in memory. Skip the run files, and write straight to `output`.
+                for record in chunk.iter() {
+                    self.codec.write(&mut output, record)?;
+                }
+                return Ok(());
+            }
+            if !chunk.is_empty() {
+                let run = Run::create(&self.temp_dir)?;
+                let mut writer = BufWriter::new(File::create(&run.path)?);
+                for record in chunk.iter() {
+                    self.codec.write(&mut writer, record)?;
+                }
+                writer.flush()?;
+                runs.push(run);
+            }
+            if done {
+                break;
+            }
+        }
+
+        // Merge down to at most `fan_in` runs, in passes over consecutive groups.
+        // Merging consecutive runs keeps equal records in input order.
+        let fan_in = self.fan_in.max(2);
+        while runs.len() > fan_in {
+            let mut merged = Vec::new();
+            let mut iter = runs.into_iter();
+            loop {
+                let group: Vec<Run> = iter.by_ref().take(fan_in).collect();
+                if group.is_empty() {
+                    break;
+                }
+                let run = Run::create(&self.temp_dir)?;
+                let mut writer = BufWriter::new(File::create(&run.path)?);
+                self.merge(&group, &mut writer, &cmp)?;
+                writer.flush()?;
+                merged.push(run);
+            }
+            runs = merged;
+        }
+        // And finally into `output`
+        self.merge(&runs, &mut output, &cmp)
+    }
+    /// Read records from `reader` until the memory budget is reached.
+    /// Returns the records, and a boolean indication of whether `reader` is exhausted.
+    /// Always reads at least one record, if available.
+    fn read_chunk<R: BufRead>(&self, reader: &mut R) -> io::Result<(Vec<C::Record>, bool)> {
+        let mut chunk = Vec::new();
+        let mut used = 0;
+        while chunk.is_empty() || used < self.memory {
+            match self.codec.read(reader)? {
+                None => return Ok((chunk, true)),
+                Some(record) => {
+                    used += self.codec.size(&record);
+                    chunk.push(record);
+                }
+            }
+        }
+        Ok((chunk, false))
+    }
+    /// K-way merge each of `runs` into `output`.
+    /// Ties are broken by run-index, so that equal records retain their relative order.
+    fn merge<W, F>(&self, runs: &[Run], output: &mut W, cmp: &F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&C::Record, &C::Record) -> Ordering,
+    {
+        let mut readers = runs
+            .iter()
+            .map(|run| Ok(BufReader::new(File::open(&run.path)?)))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        // Heap of each run's head record, paired with its run-index
+        let mut heap = Heap::with_comparator(|a: &(C::Record, usize), b: &(C::Record, usize)| {
+            cmp(&a.0, &b.0).then(a.1.cmp(&b.1))
+        });
+        for (idx, reader) in readers.iter_mut().enumerate() {
+            if let Some(record) = self.codec.read(reader)? {
+                heap.push((record, idx));
+            }
+        }
+        // Repeatedly write the top record, and replace it with the next from its run
+        while let Some((record, idx)) = heap.pop() {
+            self.codec.write(output, &record)?;
+            if let Some(next) = self.codec.read(&mut readers[idx])? {
+                heap.push((next, idx));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Counter for unique run-file names
+static RUN_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Temporary run file, removed on drop.
+/// Also used for [ExternalSort::sort_file]'s output, which is renamed away before the drop.
+#[derive(Debug)]
+struct Run {
+    path: PathBuf,
+}
+impl Run {
+    /// Create a new, uniquely-named run path in directory `dir`
+    fn create(dir: &Path) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let count = RUN_COUNTER.fetch_add(1, AtomicOrdering::Relaxed);
+        let name = format!("ctci-external-sort-{}-{}.run", std::process::id(), count);
+        Ok(Self {
+            path: dir.join(name),
+        })
+    }
+}
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+/// Create a path for test-directory `name` in the system temporary directory, unique to this process
+fn test_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "ctci-test-external-sort-{}-{}",
+        name,
+        std::process::id()
+    ))
+}
+
+#[test]
+fn test_external_sort_lines() -> io::Result<()> {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(34);
+    let lines: Vec<String> = (0..5_000)
+        .map(|_| format!("{:08}", rng.gen_range(0..100_000)))
+        .collect();
+    let input = lines.join("\n");
+
+    // A tiny memory budget and fan-in, to force several runs and merge passes
+    let dir = test_dir("lines");
+    let sorter = ExternalSort::new(LineCodec)
+        .memory(4 * 1024)
+        .fan_in(3)
+        .temp_dir(&dir);
+    let mut output = Vec::new();
+    sorter.sort(input.as_bytes(), &mut output)?;
+
+    let mut correct = lines.clone();
+    correct.sort();
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(output.lines().collect::<Vec<_>>(), correct);
+
+    // Run files are all cleaned up
+    assert_eq!(fs::read_dir(&dir)?.count(), 0);
+    fs::remove_dir(&dir)
+}
+
+#[test]
+fn test_external_sort_fixed_width() -> io::Result<()> {
+    // Two-byte records, sorted by their first byte only, so stability is observable
+    let input: Vec<u8> = (0..1_000u32)
+        .flat_map(|i| vec![(i * 7 % 13) as u8, (i % 256) as u8])
+        .collect();
+    let dir = test_dir("fixed");
+    let sorter = ExternalSort::new(FixedWidthCodec::new(2))
+        .memory(1024)
+        .temp_dir(&dir);
+    let mut output = Vec::new();
+    sorter.sort_by(&input[..], &mut output, |a, b| a[0].cmp(&b[0]))?;
+
+    let mut correct: Vec<&[u8]> = input.chunks(2).collect();
+    correct.sort_by_key(|r| r[0]);
+    assert_eq!(output.chunks(2).collect::<Vec<_>>(), correct);
+
+    // Partial trailing records are an error
+    let result = sorter.sort(&input[..3], &mut Vec::new());
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+
+    // As are zero-width records, which would otherwise never consume any input
+    let zero = ExternalSort::new(FixedWidthCodec { width: 0 }).temp_dir(&dir);
+    let result = zero.sort(&input[..], &mut Vec::new());
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    assert!(std::panic::catch_unwind(|| FixedWidthCodec::new(0)).is_err());
+    fs::remove_dir_all(&dir)
+}
+
+#[test]
+fn test_external_sort_file() -> io::Result<()> {
+    let dir = test_dir("file");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("records.txt");
+    fs::write(&path, "c\nb\nd\na\n")?;
+    let sorter = ExternalSort::new(LineCodec).memory(16).temp_dir(&dir);
+
+    // Sorting a file onto itself
+    sorter.sort_file(&path, &path)?;
+    assert_eq!(fs::read_to_string(&path)?, "a\nb\nc\nd\n");
+
+    // And into a separate file, leaving the input intact
+    let sorted = dir.join("sorted.txt");
+    fs::write(&path, "y\nx\n")?;
+    sorter.sort_file(&path, &sorted)?;
+    assert_eq!(fs::read_to_string(&path)?, "y\nx\n");
+    assert_eq!(fs::read_to_string(&sorted)?, "x\ny\n");
+
+    // Failures leave any existing output untouched, and no temporary files behind
+    assert!(sorter.sort_file(dir.join("missing.txt"), &sorted).is_err());
+    assert_eq!(fs::read_to_string(&sorted)?, "x\ny\n");
+    assert_eq!(fs::read_dir(&dir)?.count(), 2);
+    fs::remove_dir_all(&dir)
+}
+
+#[test]
+fn test_external_sort_in_memory() -> io::Result<()> {
+    // Inputs within the memory budget never touch the file-system
+    let sorter = ExternalSort::new(LineCodec).temp_dir("/nonexistent/ctci");
+    let mut output = Vec::new();
+    sorter.sort(&b"c\r\nb\na\n"[..], &mut output)?;
+    assert_eq!(output, b"a\nb\nc\n");
+
+    let mut output = Vec::new();
+    sorter.sort(&b""[..], &mut output)?;
+    assert!(output.is_empty());
+    Ok(())
+}
@@ -1,29 +1,143 @@
 //!
-//! # Binary (Min) Heap
+//! # Binary Heap
+//!
+//! Min-ordered by default, with max-ordering and custom comparators available via the [Compare] trait.
+//!
+//! Also defines the [PriorityQueue] trait, shared with the mergeable heaps in
+//! [crate::pairing_heap] and [crate::binomial_heap].
 //!
 
-/// # Binary (Min) Heap
+use std::{cell::RefCell, cmp::Ordering, iter::FromIterator, ops::Deref, rc::Rc};
+
+///
+/// # Priority Queue
+///
+/// Common interface to the crate's heap variants.
+/// All are ordered by a [Compare] comparator, and pop their highest-priority element first.
+///
+pub trait PriorityQueue<T> {
+    /// Reference-type returned by `peek`.
+    /// A plain `&T` for array-based heaps, or a [std::cell::Ref] for those with shared nodes.
+    type Peek<'a>: Deref<Target = T>
+    where
+        Self: 'a;
+
+    /// Push an element into the queue
+    fn push(&mut self, t: T);
+    /// Pop the top element. Returns `None` if empty.
+    fn pop(&mut self) -> Option<T>;
+    /// Get a reference to the top element, without removing it. Returns `None` if empty.
+    fn peek(&self) -> Option<Self::Peek<'_>>;
+    /// Get the number of elements in the queue
+    fn len(&self) -> usize;
+    /// Boolean indication of whether the queue is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Merge all elements of `other` into this queue
+    fn meld(&mut self, other: Self)
+    where
+        Self: Sized;
+}
+
+///
+/// # Heap-Ordering Comparator
+///
+/// Dictates which of two elements sits closer to the top of a heap.
+/// The element which compares [Ordering::Less] is the higher priority, and is popped first.
+///
+/// Implemented by the unit-structs [MinOrder] and [MaxOrder],
+/// and by any closure of the form `Fn(&T, &T) -> Ordering`.
+///
+pub trait Compare<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Min-Ordering, the [Heap] default. Smallest elements come out first.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MinOrder;
+impl<T: Ord> Compare<T> for MinOrder {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Max-Ordering. Largest elements come out first.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MaxOrder;
+impl<T: Ord> Compare<T> for MaxOrder {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+// Custom comparators, via closures
+impl<T, F: Fn(&T, &T) -> Ordering> Compare<T> for F {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+///
+/// # Binary Heap
 ///
 /// Built on Rust's standard-library [Vec].
+/// Ordering is set by comparator-type `C`, which defaults to [MinOrder].
 ///
-#[derive(Debug)]
-pub struct Heap<T> {
+#[derive(Debug, Clone)]
+pub struct Heap<T, C = MinOrder> {
     data: Vec<T>,
+    cmp: C,
 }
+
+/// Alias for a min-ordered [Heap]
+pub type MinHeap<T> = Heap<T, MinOrder>;
+/// Alias for a max-ordered [Heap]
+pub type MaxHeap<T> = Heap<T, MaxOrder>;
+
 impl<T> Default for Heap<T> {
     fn default() -> Self {
-        Self { data: Vec::new() }
+        Self {
+            data: Vec::new(),
+            cmp: MinOrder,
+        }
     }
 }
 impl<T: Ord> Heap<T> {
+    /// Create a new, empty min-heap. Also available via [Default].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl<T: Ord> Heap<T, MaxOrder> {
+    /// Create a new, empty max-heap
+    pub fn new_max() -> Self {
+        Self::with_comparator(MaxOrder)
+    }
+}
+impl<T, C: Compare<T>> Heap<T, C> {
+    /// Create a new, empty heap ordered by comparator `cmp`
+    pub fn with_comparator(cmp: C) -> Self {
+        Self {
+            data: Vec::new(),
+            cmp,
+        }
+    }
+    /// Create a heap from the elements of `data`, ordered by comparator `cmp`.
+    /// Heapifies in O(n) time, re-using the allocation of `data`.
+    pub fn from_vec_with(mut data: Vec<T>, cmp: C) -> Self {
+        heapify(&mut data, before(&cmp));
+        Self { data, cmp }
+    }
     /// Push an element onto the heap
     pub fn push(&mut self, t: T) {
         // Push to the end of `data`
         self.data.push(t);
         // And re-establish the heap property
-        self.siftup(self.data.len() - 1)
+        let last = self.data.len() - 1;
+        siftup(&mut self.data, last, &self.cmp)
     }
-    /// Pop the min element from the heap. Returns `None` if empty.
+    /// Pop the top element from the heap. Returns `None` if empty.
     pub fn pop(&mut self) -> Option<T> {
         if self.data.is_empty() {
             return None;
@@ -33,56 +147,249 @@ impl<T: Ord> Heap<T> {
         self.data.swap(0, last);
 
         // Pop the return/ head-element off the end
-        let rv = self.data.pop().unwrap();
+        let rv = self.data.pop();
 
-        if !self.data.is_empty() {
-            self.siftdown(0); // Get to reorganizing
+        // And get to reorganizing
+        siftdown(&mut self.data, 0, before(&self.cmp));
+        rv
+    }
+    /// Get a reference to the top element, without removing it. Returns `None` if empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+    /// Push `t`, then pop and return the top element.
+    /// More efficient than separate calls to `push` and `pop`.
+    pub fn push_pop(&mut self, mut t: T) -> T {
+        match self.data.first_mut() {
+            Some(top) if self.cmp.compare(top, &t) == Ordering::Less => {
+                // Existing top out-ranks `t`. Swap it in, and sift down.
+                std::mem::swap(top, &mut t);
+                siftdown(&mut self.data, 0, before(&self.cmp));
+                t
+            }
+            _ => t, // Empty, or `t` would come right back out
         }
-
-        Some(rv)
-    }
-    /// Sift downward from index `idx`. Re-arranges children during insertion.
-    fn siftdown(&mut self, idx: usize) {
-        let val = &self.data[idx];
-        let left = self.data.get(2 * idx + 1);
-        let right = self.data.get(2 * idx + 2);
-        match (left, right) {
-            (None, None) => {} // No children, nothing to swap
-            (Some(lf), None) => {
-                // Left but no right child. Swap if out of order.
-                if lf < &self.data[idx] {
-                    self.data.swap(2 * idx + 1, idx);
-                }
+    }
+    /// Pop the top element, then push `t`. Returns `None`, and leaves `t` in place, if empty.
+    /// More efficient than separate calls to `pop` and `push`.
+    pub fn replace(&mut self, mut t: T) -> Option<T> {
+        match self.data.first_mut() {
+            None => {
+                self.data.push(t);
+                None
             }
-            (Some(lf), Some(rt)) => {
-                // Two children, potentially recursively update
-                let min = lf.min(rt).min(val);
-                if min == val {
-                    // Min on top, already done
-                } else if min == lf {
-                    // Left-child is the min. Swap with it, and recursively sift down again.
-                    self.data.swap(2 * idx + 1, idx);
-                    self.siftdown(2 * idx + 1)
-                } else {
-                    // Right-child is the min. Swap with it, and recursively sift down again.
-                    self.data.swap(2 * idx + 2, idx);
-                    self.siftdown(2 * idx + 2)
-                }
+            Some(top) => {
+                std::mem::swap(top, &mut t);
+                siftdown(&mut self.data, 0, before(&self.cmp));
+                Some(t)
             }
-            _ => unreachable!("Internal Error"), // The fourth case - right child but no left - shouldn't be possible
-        };
+        }
+    }
+    /// Get the number of elements in the heap
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    /// Boolean indication of whether the heap is empty
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    /// Remove all elements
+    pub fn clear(&mut self) {
+        self.data.clear()
+    }
+    /// Iterate over all elements, in arbitrary (internal storage) order
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+    /// Merge all elements of `other` into this heap.
+    /// Runs in O(n + m) time, appending `other` and re-heapifying.
+    /// See [crate::pairing_heap] and [crate::binomial_heap] for faster alternatives.
+    pub fn meld(&mut self, mut other: Self) {
+        self.data.append(&mut other.data);
+        heapify(&mut self.data, before(&self.cmp));
+    }
+    /// Convert into a [Vec], in arbitrary (internal storage) order
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+    /// Convert into a [Vec], sorted in ascending order, as does [std::collections::BinaryHeap::into_sorted_vec].
+    /// Ascending regardless of comparator: a [MaxHeap] and a [MinHeap] of the same elements give the same result.
+    /// See [Heap::into_pop_order_vec] for the comparator's order.
+    pub fn into_sorted_vec(self) -> Vec<T>
+    where
+        T: Ord,
+    {
+        // Pop-order is a single ascending or descending run for [MinOrder] and [MaxOrder],
+        // which the standard-library sort handles in linear time
+        let mut v = self.into_pop_order_vec();
+        v.sort();
+        v
+    }
+    /// Convert into a [Vec], sorted in the order in which `pop` would return its elements.
+    /// Sorts in place, via heap-sort, without further allocation.
+    pub fn into_pop_order_vec(mut self) -> Vec<T> {
+        // Repeatedly swap the top to the end of the shrinking heap-region.
+        // This leaves elements in reverse pop-order, which we reverse at the end.
+        for end in (1..self.data.len()).rev() {
+            self.data.swap(0, end);
+            siftdown(&mut self.data[..end], 0, before(&self.cmp));
+        }
+        self.data.reverse();
+        self.data
+    }
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T> for Heap<T, C> {
+    type Peek<'a>
+        = &'a T
+    where
+        Self: 'a;
+
+    fn push(&mut self, t: T) {
+        Heap::push(self, t)
+    }
+    fn pop(&mut self) -> Option<T> {
+        Heap::pop(self)
     }
-    /// Sift upwards from `idx`. Re-arranges parents during removal.
-    fn siftup(&mut self, idx: usize) {
-        if idx == 0 {
-            return; // At root/ head node, no parents to swap with
+    fn peek(&self) -> Option<&T> {
+        Heap::peek(self)
+    }
+    fn len(&self) -> usize {
+        Heap::len(self)
+    }
+    fn meld(&mut self, other: Self) {
+        Heap::meld(self, other)
+    }
+}
+
+impl<T, C: Compare<T> + Default> From<Vec<T>> for Heap<T, C> {
+    /// Heapify `data` in O(n) time
+    fn from(data: Vec<T>) -> Self {
+        Self::from_vec_with(data, C::default())
+    }
+}
+impl<T, C: Compare<T> + Default> FromIterator<T> for Heap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+impl<T, C: Compare<T>> Extend<T> for Heap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.push(t);
+        }
+    }
+}
+
+/// Drain `queue` into a [Vec], in pop-order.
+/// Generic over all [PriorityQueue] implementations.
+pub fn drain_sorted<T, Q: PriorityQueue<T>>(queue: &mut Q) -> Vec<T> {
+    let mut v = Vec::with_capacity(queue.len());
+    while let Some(t) = queue.pop() {
+        v.push(t);
+    }
+    v
+}
+
+///
+/// # Heap Identity
+///
+/// Shared by a mergeable heap and each of its elements, so that handles issued by other heaps can be rejected.
+/// Melding forwards the absorbed heap's identity to the surviving heap's, rather than re-tagging its elements.
+/// Lookups follow the chain of forwards, and compress it, as in a union-find structure.
+///
+#[derive(Debug, Default)]
+pub(crate) struct Owner {
+    forward: RefCell<Option<Rc<Owner>>>,
+}
+impl Owner {
+    /// Create a new, unique identity
+    pub(crate) fn new() -> Rc<Self> {
+        Rc::new(Self::default())
+    }
+    /// Forward `absorbed`, the identity of a heap being melded into ours, to `this`
+    pub(crate) fn absorb(this: &Rc<Self>, absorbed: &Rc<Self>) {
+        *absorbed.forward.borrow_mut() = Some(Rc::clone(this));
+    }
+    /// Boolean indication of whether `this` has been forwarded, directly or otherwise, to `owner`.
+    /// `owner` must be a live heap's identity, which is never itself forwarded.
+    pub(crate) fn is_owned_by(this: &Rc<Self>, owner: &Rc<Self>) -> bool {
+        // Find the end of the chain, then point every identity along it there directly
+        let mut root = Rc::clone(this);
+        loop {
+            let next = root.forward.borrow().clone();
+            match next {
+                Some(next) => root = next,
+                None => break,
+            }
+        }
+        let mut node = Rc::clone(this);
+        while !Rc::ptr_eq(&node, &root) {
+            let next = node.forward.replace(Some(Rc::clone(&root))).unwrap();
+            node = next;
+        }
+        Rc::ptr_eq(&root, owner)
+    }
+}
+
+/// Convert comparator `cmp` into the "sits closer to the top" predicate of [heapify] and [siftdown]
+fn before<T, C: Compare<T>>(cmp: &C) -> impl FnMut(&T, &T) -> bool + '_ {
+    move |a, b| cmp.compare(a, b) == Ordering::Less
+}
+
+/// Re-arrange `data` to satisfy the heap property, in O(n) time.
+/// Sifts down from each non-leaf, starting from the last.
+/// Returns the number of swaps made.
+///
+/// Predicate `before(a, b)` indicates whether `a` out-ranks `b`, i.e. sits closer to the top.
+/// [crate::sort::HeapSort] passes one which counts its comparisons.
+pub(crate) fn heapify<T, F>(data: &mut [T], mut before: F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    (0..data.len() / 2)
+        .rev()
+        .map(|idx| siftdown(data, idx, &mut before))
+        .sum()
+}
+
+/// Sift downward from index `idx`. Re-arranges children during removal.
+/// Returns the number of swaps made. Predicate `before` is as for [heapify].
+pub(crate) fn siftdown<T, F>(data: &mut [T], mut idx: usize, mut before: F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut swaps = 0;
+    loop {
+        let (left, right) = (2 * idx + 1, 2 * idx + 2);
+        if left >= data.len() {
+            return swaps; // No children, nothing to swap
         }
-        let val = &self.data[idx];
-        let parent = &self.data[(idx - 1) / 2];
-        if val < parent {
-            self.data.swap(idx, (idx - 1) / 2);
-            self.siftup((idx - 1) / 2)
+        // Find the higher-priority child
+        let child = if right < data.len() && before(&data[right], &data[left]) {
+            right
+        } else {
+            left
+        };
+        if !before(&data[child], &data[idx]) {
+            return swaps; // Already in order, done
         }
+        // Swap with that child, and continue down from it
+        data.swap(child, idx);
+        swaps += 1;
+        idx = child;
+    }
+}
+
+/// Sift upwards from `idx`. Re-arranges parents during insertion.
+fn siftup<T, C: Compare<T>>(data: &mut [T], mut idx: usize, cmp: &C) {
+    while idx > 0 {
+        let parent = (idx - 1) / 2;
+        if cmp.compare(&data[idx], &data[parent]) != Ordering::Less {
+            return; // Already in order, done
+        }
+        data.swap(idx, parent);
+        idx = parent;
     }
 }
 
@@ -156,3 +463,81 @@ fn test_heap4() {
     }
     assert_eq!(heap.pop(), None);
 }
+
+#[test]
+fn test_heap_peek_len() {
+    let mut heap = Heap::new();
+    assert!(heap.is_empty());
+    assert_eq!(heap.peek(), None);
+
+    heap.extend(vec![5, 3, 8, 1]);
+    assert_eq!(heap.len(), 4);
+    assert_eq!(heap.peek(), Some(&1));
+    assert_eq!(heap.push_pop(0), 0);
+    assert_eq!(heap.push_pop(4), 1);
+    assert_eq!(heap.replace(9), Some(3));
+    assert_eq!(heap.into_sorted_vec(), vec![4, 5, 8, 9]);
+}
+
+#[test]
+fn test_heap_max_and_custom() {
+    let items = vec![10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5];
+
+    // Max-ordering
+    let mut heap = MaxHeap::from(items.clone());
+    assert_eq!(heap.peek(), Some(&10));
+    assert_eq!(heap.pop(), Some(10));
+    assert_eq!(heap.pop(), Some(9));
+    assert_eq!(heap.len(), 9);
+
+    // Custom comparator: order by distance from 5
+    let mut heap = Heap::with_comparator(|a: &i32, b: &i32| (a - 5).abs().cmp(&(b - 5).abs()));
+    heap.extend(items.iter().copied());
+    assert_eq!(heap.pop(), Some(5));
+    let next = heap.pop().unwrap();
+    assert!(next == 4 || next == 6);
+}
+
+#[test]
+fn test_heapify() {
+    let items = vec![10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5];
+
+    let heap: Heap<i32> = items.iter().copied().collect();
+    assert_eq!(heap.len(), items.len());
+    assert_eq!(heap.into_sorted_vec(), (0..=10).collect::<Vec<_>>());
+
+    let heap: MaxHeap<i32> = items.iter().copied().collect();
+    assert_eq!(
+        heap.into_pop_order_vec(),
+        (0..=10).rev().collect::<Vec<_>>()
+    );
+    let heap: MaxHeap<i32> = items.iter().copied().collect();
+    assert_eq!(heap.into_sorted_vec(), (0..=10).collect::<Vec<_>>());
+    let heap = Heap::from_vec_with(items.clone(), |a: &i32, b: &i32| (a % 3).cmp(&(b % 3)));
+    assert_eq!(heap.into_sorted_vec(), (0..=10).collect::<Vec<_>>());
+
+    // Compare against the standard library's [BinaryHeap]
+    let mut ours = Heap::new_max();
+    let mut theirs = std::collections::BinaryHeap::new();
+    for k in [4, 4, 1, 9, 2, 7, 7, 0, 3] {
+        ours.push(k);
+        theirs.push(k);
+    }
+    assert_eq!(
+        ours.clone().into_sorted_vec(),
+        theirs.clone().into_sorted_vec()
+    );
+    while let Some(k) = theirs.pop() {
+        assert_eq!(ours.pop(), Some(k));
+    }
+    assert!(ours.is_empty());
+}
+
+#[test]
+fn test_heap_meld() {
+    let mut a: Heap<i32> = vec![9, 1, 5].into();
+    let b: Heap<i32> = vec![4, 8, 0].into();
+    PriorityQueue::meld(&mut a, b);
+    assert_eq!(PriorityQueue::len(&a), 6);
+    assert_eq!(drain_sorted(&mut a), vec![0, 1, 4, 5, 8, 9]);
+}
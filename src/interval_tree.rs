@@ -0,0 +1,675 @@
+//!
+//! # Interval Tree
+//!
+//! A centered interval tree of half-open `[start, end)` [Interval]s,
+//! answering which stored intervals overlap a point, or another interval.
+//!
+//! Built on the index-based [BstMap] of [crate::binary_search_tree], AVL-balanced.
+//! Its keys, the *centers*, are the start-points of the stored intervals.
+//! Each interval is held by the first center on the search path to its start which it contains,
+//! in two lists: one ordered by start, and one by descending end.
+//! A rotation may lift a new parent above a center; the map's augmentation callback then moves
+//! any of the center's intervals which contain the parent up into it.
+//!
+//! All intervals containing a point are held along the point's search path.
+//! Each center on that path reports a prefix of one of its lists, stopping at the first interval
+//! which misses the point. Listing all k intervals containing a point therefore takes O(log n + k) time.
+//! Interval queries add those starting inside the query, walked in start-order from a second map,
+//! and also take O(log n + k). Finding a single overlap takes O(log n).
+//!
+//! Insertion and removal take O(log n) time, plus O(log n) for each interval which a rotation moves
+//! between centers. Centers are not removed individually. Once unused centers outnumber the
+//! stored intervals, all are rebuilt, for an amortized O(log n) per removal.
+//!
+//! Closed index-[Range]s, as produced by [crate::chapter_17::p18_shortest_supersequence],
+//! convert into [Interval]s via [TryFrom], and the standard library's half-open [std::ops::Range]s via [From].
+//!
+
+// Std-Lib Imports
+use std::cmp::Reverse;
+use std::convert::TryFrom;
+
+// Local Imports
+use crate::binary_search_tree::{Avl, AvlMap, BstMap, NodeIndex};
+use crate::chapter_17::p18_shortest_supersequence::Range;
+
+///
+/// # Interval
+///
+/// Half-open: includes `start`, excludes `end`.
+/// Empty intervals, with `start == end`, overlap nothing.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+impl<T: Ord> Interval<T> {
+    /// Create a new [Interval]. Returns an [Error] if `start` is after `end`.
+    pub fn new(start: T, end: T) -> Result<Self, Error> {
+        if start > end {
+            return Err(Error);
+        }
+        Ok(Self { start, end })
+    }
+    /// Boolean indication of whether the interval is empty
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+    /// Boolean indication of whether the interval contains point `p`
+    pub fn contains(&self, p: &T) -> bool {
+        self.start <= *p && *p < self.end
+    }
+    /// Boolean indication of whether the interval overlaps `other`, i.e. whether they share any point
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        !self.is_empty() && !other.is_empty() && self.start < other.end && other.start < self.end
+    }
+}
+impl<T> From<std::ops::Range<T>> for Interval<T> {
+    fn from(r: std::ops::Range<T>) -> Self {
+        Self {
+            start: r.start,
+            end: r.end,
+        }
+    }
+}
+impl TryFrom<Range> for Interval<usize> {
+    type Error = Error;
+    /// Convert closed index-range `[min, max]` to the half-open `[min, max + 1)`.
+    /// Returns an [Error] if `max` is `usize::MAX`, leaving no room for the exclusive end,
+    /// or if `min` is after `max`.
+    fn try_from(r: Range) -> Result<Self, Error> {
+        let end = r.max.checked_add(1).ok_or(Error)?;
+        Self::new(r.min, end)
+    }
+}
+
+///
+/// # Interval List
+///
+/// The intervals held by a [Center], ordered by key `K`, each with its opposite endpoint.
+/// Caches the index of its first entry, so that scans begin in O(1) time.
+///
+#[derive(Debug)]
+struct List<K, T> {
+    map: AvlMap<K, T>,
+    first: Option<NodeIndex>,
+}
+impl<K: Ord, T> List<K, T> {
+    /// Create a new, empty list
+    fn new() -> Self {
+        Self {
+            map: BstMap::new_avl(),
+            first: None,
+        }
+    }
+    /// Get the first entry, if any
+    fn first(&self) -> Option<(&K, &T)> {
+        self.first
+            .map(|idx| (&self.map[idx].key, &self.map[idx].value))
+    }
+    /// Insert `key`, with opposite endpoint `other`
+    fn insert(&mut self, key: K, other: T) {
+        self.map.insert(key, other);
+        self.first = self.map.min();
+    }
+    /// Remove `key`, returning its opposite endpoint
+    fn remove(&mut self, key: &K) -> Option<T> {
+        let other = self.map.remove(key);
+        self.first = self.map.min();
+        other
+    }
+}
+
+///
+/// # Center
+///
+/// Value stored at each center-point `c`: the intervals it holds, i.e. those containing `c`
+/// and none of its ancestors. Both lists identify intervals by their `id`, their slot in
+/// [IntervalTree]'s `entries`.
+///
+#[derive(Debug)]
+struct Center<T> {
+    /// Number of stored intervals starting at this center
+    refs: usize,
+    /// Held intervals, ordered by start, to their ends
+    by_start: List<(T, usize), T>,
+    /// Held intervals, ordered by descending end, to their starts
+    by_end: List<(Reverse<T>, usize), T>,
+}
+
+/// Underlying map of centers
+type Centers<T> = BstMap<T, Center<T>, Avl>;
+
+impl<T: Ord + Clone> Center<T> {
+    /// Create a new, empty center
+    fn new() -> Self {
+        Self {
+            refs: 0,
+            by_start: List::new(),
+            by_end: List::new(),
+        }
+    }
+    /// Hold interval `id`, spanning `[start, end)`
+    fn add(&mut self, start: T, end: T, id: usize) {
+        self.by_start.insert((start.clone(), id), end.clone());
+        self.by_end.insert((Reverse(end), id), start);
+    }
+    /// Release interval `id`, spanning `[start, end)`
+    fn take(&mut self, start: &T, end: &T, id: usize) {
+        self.by_start.remove(&(start.clone(), id));
+        self.by_end.remove(&(Reverse(end.clone()), id));
+    }
+    /// Get the next held interval containing `point`, from whichever list is ordered toward it.
+    /// Returns its `(start, end, id)`.
+    fn next_containing(&self, point: &T, below: bool) -> Option<(T, T, usize)> {
+        if below {
+            let ((start, id), end) = self.by_start.first()?;
+            (start <= point).then(|| (start.clone(), end.clone(), *id))
+        } else {
+            let ((Reverse(end), id), start) = self.by_end.first()?;
+            (end > point).then(|| (start.clone(), end.clone(), *id))
+        }
+    }
+    /// Augmentation callback for the map of centers.
+    /// Moves each interval held by `idx` which contains its parent's point up into the parent,
+    /// which is only possible after a rotation lifts the parent above `idx`.
+    fn migrate(centers: &mut Centers<T>, idx: NodeIndex) {
+        let parent = match centers[idx].parent {
+            Some(p) if !centers[idx].value.by_start.map.is_empty() => p,
+            _ => return,
+        };
+        let point = centers[parent].key.clone();
+        let below = point < centers[idx].key;
+        let center = &mut centers[idx].value;
+        let mut moved = Vec::new();
+        while let Some((start, end, id)) = center.next_containing(&point, below) {
+            center.take(&start, &end, id);
+            moved.push((start, end, id));
+        }
+        for (start, end, id) in moved {
+            centers[parent].value.add(start, end, id);
+        }
+    }
+    /// Check that both lists hold the same intervals, and that their cached first entries are current
+    fn check(&self) -> bool {
+        let (starts, ends) = (&self.by_start.map, &self.by_end.map);
+        starts.check_invariants()
+            && ends.check_invariants()
+            && starts.len() == ends.len()
+            && self.by_start.first == starts.min()
+            && self.by_end.first == ends.min()
+            && starts
+                .iter()
+                .all(|((start, id), end)| ends.get(&(Reverse(end.clone()), *id)) == Some(start))
+    }
+}
+
+/// Map from `(interval, sequence-number)` keys to `id`s in [IntervalTree]'s `entries`
+type Sorted<T> = AvlMap<(Interval<T>, usize), usize>;
+
+/// Centers are rebuilt once they number more than twice the stored intervals, plus this slack
+const REBUILD_SLACK: usize = 16;
+
+///
+/// # Interval Tree
+///
+/// Maps [Interval]s to values `V`. The same interval may be stored more than once.
+///
+#[derive(Debug)]
+pub struct IntervalTree<T, V> {
+    /// Stored intervals and their values, indexed by `id`. Vacated slots are re-used.
+    entries: Vec<Option<(Interval<T>, V)>>,
+    /// Vacated slots of `entries`
+    free: Vec<usize>,
+    /// Non-empty intervals, in `(start, end)` and then insertion order
+    sorted: Sorted<T>,
+    /// Empty intervals, which are stored but never overlap anything
+    empties: Sorted<T>,
+    /// Centers holding the non-empty intervals
+    centers: Centers<T>,
+    /// Sequence-number of the next insertion
+    seq: usize,
+}
+impl<T: Ord + Clone, V> Default for IntervalTree<T, V> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            free: Vec::new(),
+            sorted: BstMap::new_avl(),
+            empties: BstMap::new_avl(),
+            centers: BstMap::augmented(Center::migrate),
+            seq: 0,
+        }
+    }
+}
+impl<T: Ord + Clone, V> IntervalTree<T, V> {
+    /// Create a new, empty tree. Also available via [Default].
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Get the number of stored intervals
+    pub fn len(&self) -> usize {
+        self.sorted.len() + self.empties.len()
+    }
+    /// Boolean indication of whether the tree is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Insert `interval`, with associated `value`
+    pub fn insert(&mut self, interval: Interval<T>, value: V) {
+        let id = match self.free.pop() {
+            Some(id) => id,
+            None => {
+                self.entries.push(None);
+                self.entries.len() - 1
+            }
+        };
+        self.entries[id] = Some((interval.clone(), value));
+        let key = (interval.clone(), self.seq);
+        self.seq += 1;
+        if interval.is_empty() {
+            self.empties.insert(key, id);
+            return;
+        }
+        self.sorted.insert(key, id);
+        self.centers
+            .entry(interval.start.clone())
+            .or_insert_with(Center::new)
+            .refs += 1;
+        // The interval contains its own start, so some center on the way there holds it
+        let fork = Self::fork(&self.centers, &interval).unwrap();
+        self.centers[fork]
+            .value
+            .add(interval.start, interval.end, id);
+    }
+    /// Remove one occurrence of `interval`, the earliest inserted, returning its value.
+    /// Returns `None` if not present.
+    pub fn remove(&mut self, interval: &Interval<T>) -> Option<V> {
+        let first = (interval.clone(), 0)..=(interval.clone(), usize::MAX);
+        let map = match interval.is_empty() {
+            true => &mut self.empties,
+            false => &mut self.sorted,
+        };
+        let key = map.range(first).next()?.0.clone();
+        let id = map.remove(&key)?;
+        if !interval.is_empty() {
+            let fork = Self::fork(&self.centers, interval).unwrap();
+            self.centers[fork]
+                .value
+                .take(&interval.start, &interval.end, id);
+            self.centers.get_mut(&interval.start).unwrap().refs -= 1;
+            if self.centers.len() > 2 * self.sorted.len() + REBUILD_SLACK {
+                self.rebuild();
+            }
+        }
+        self.free.push(id);
+        self.entries[id].take().map(|(_, value)| value)
+    }
+    /// Find any single interval overlapping `query`, in O(log n) time
+    pub fn find_overlap(&self, query: &Interval<T>) -> Option<(&Interval<T>, &V)> {
+        self.query(query.clone()).next()
+    }
+    /// Get an iterator over all intervals overlapping `query`
+    pub fn query(&self, query: Interval<T>) -> Overlaps<'_, T, V> {
+        let (center, after) = match query.is_empty() {
+            true => (None, None),
+            false => (self.centers.head, self.first_after(&query.start)),
+        };
+        Overlaps {
+            tree: self,
+            point: query.start,
+            center,
+            scan: Scan::Done,
+            end: Some(query.end),
+            after,
+        }
+    }
+    /// Get an iterator over all intervals containing point `p`
+    pub fn query_point(&self, p: T) -> Overlaps<'_, T, V> {
+        Overlaps {
+            tree: self,
+            point: p,
+            center: self.centers.head,
+            scan: Scan::Done,
+            end: None,
+            after: None,
+        }
+    }
+    /// Check the underlying maps' invariants, and that each center holds exactly the intervals it should
+    pub fn check_invariants(&self) -> bool {
+        let maps_ok = self.centers.check_invariants()
+            && self.sorted.check_invariants()
+            && self.empties.check_invariants();
+        let entries_ok = self.entries.iter().flatten().count() == self.len()
+            && self.sorted.iter().chain(self.empties.iter()).all(
+                |((interval, _), &id)| matches!(&self.entries[id], Some((i, _)) if i == interval),
+            );
+        let mut held = 0;
+        let centers_ok = self.centers.nodes.iter().all(|node| {
+            let center = &node.value;
+            held += center.by_start.map.len();
+            let refs = self
+                .sorted
+                .keys()
+                .filter(|(i, _)| i.start == node.key)
+                .count();
+            center.refs == refs
+                && center.check()
+                && center.by_start.map.iter().all(|((start, id), end)| {
+                    let interval = Interval {
+                        start: start.clone(),
+                        end: end.clone(),
+                    };
+                    let stored = matches!(&self.entries[*id], Some((i, _)) if *i == interval);
+                    stored && Self::fork(&self.centers, &interval) == Some(node.index)
+                })
+        });
+        maps_ok && entries_ok && centers_ok && held == self.sorted.len()
+    }
+    /// Find the center which holds non-empty `interval`:
+    /// the first on the search path to its start which it contains.
+    fn fork(centers: &Centers<T>, interval: &Interval<T>) -> Option<NodeIndex> {
+        let mut node = centers.head;
+        while let Some(idx) = node {
+            let center = &centers[idx];
+            if interval.contains(&center.key) {
+                return Some(idx);
+            }
+            node = match interval.end <= center.key {
+                true => center.left,
+                false => center.right,
+            };
+        }
+        None
+    }
+    /// Get the first non-empty interval, in start-order, which starts after point `p`
+    fn first_after(&self, p: &T) -> Option<NodeIndex> {
+        let (mut node, mut found) = (self.sorted.head, None);
+        while let Some(idx) = node {
+            let entry = &self.sorted[idx];
+            node = match entry.key.0.start > *p {
+                true => {
+                    found = Some(idx);
+                    entry.left
+                }
+                false => entry.right,
+            };
+        }
+        found
+    }
+    /// Get the interval and value stored in slot `id`
+    fn entry(&self, id: usize) -> (&Interval<T>, &V) {
+        let (interval, value) = self.entries[id].as_ref().unwrap();
+        (interval, value)
+    }
+    /// Rebuild the centers from scratch, dropping those no longer the start of any interval
+    fn rebuild(&mut self) {
+        let mut centers = BstMap::augmented(Center::migrate);
+        // Create all centers first, while they are empty and their rotations move nothing
+        for (interval, _) in self.sorted.keys() {
+            centers
+                .entry(interval.start.clone())
+                .or_insert_with(Center::new)
+                .refs += 1;
+        }
+        for ((interval, _), &id) in self.sorted.iter() {
+            let fork = Self::fork(&centers, interval).unwrap();
+            centers[fork]
+                .value
+                .add(interval.start.clone(), interval.end.clone(), id);
+        }
+        self.centers = centers;
+    }
+}
+
+/// Position within the list of a center being scanned
+#[derive(Debug, Clone, Copy)]
+enum Scan {
+    Done,
+    ByStart(NodeIndex, NodeIndex),
+    ByEnd(NodeIndex, NodeIndex),
+}
+
+///
+/// # Overlap Iterator
+///
+/// Walks the search path of a point through an [IntervalTree]'s centers, scanning each one's
+/// intervals up to the first which misses the point. Interval queries stab at their start,
+/// and then continue through the intervals starting after it, up to their end.
+/// Yields overlapping intervals in no particular order.
+///
+#[derive(Debug)]
+pub struct Overlaps<'t, T, V> {
+    tree: &'t IntervalTree<T, V>,
+    /// Stabbing point: the queried point, or the query's start
+    point: T,
+    /// Next center on the point's search path
+    center: Option<NodeIndex>,
+    /// Position within the center being scanned
+    scan: Scan,
+    /// End of the query interval, if any
+    end: Option<T>,
+    /// Next interval starting after the point, in start-order
+    after: Option<NodeIndex>,
+}
+impl<'t, T: Ord + Clone, V> Iterator for Overlaps<'t, T, V> {
+    type Item = (&'t Interval<T>, &'t V);
+    fn next(&mut self) -> Option<Self::Item> {
+        let tree = self.tree;
+        loop {
+            match self.scan {
+                Scan::ByStart(c, idx) => {
+                    let list = &tree.centers[c].value.by_start.map;
+                    let (start, id) = &list[idx].key;
+                    if *start <= self.point {
+                        self.scan = list
+                            .successor(idx)
+                            .map_or(Scan::Done, |n| Scan::ByStart(c, n));
+                        return Some(tree.entry(*id));
+                    }
+                    self.scan = Scan::Done;
+                }
+                Scan::ByEnd(c, idx) => {
+                    let list = &tree.centers[c].value.by_end.map;
+                    let (Reverse(end), id) = &list[idx].key;
+                    if *end > self.point {
+                        self.scan = list
+                            .successor(idx)
+                            .map_or(Scan::Done, |n| Scan::ByEnd(c, n));
+                        return Some(tree.entry(*id));
+                    }
+                    self.scan = Scan::Done;
+                }
+                Scan::Done => {
+                    // Move to the next center. Points before it can only be held by intervals starting early
+                    // enough, and points at or after it by those ending late enough.
+                    let c = match self.center {
+                        Some(c) => c,
+                        None => break,
+                    };
+                    let node = &tree.centers[c];
+                    if self.point < node.key {
+                        self.scan = node
+                            .value
+                            .by_start
+                            .first
+                            .map_or(Scan::Done, |i| Scan::ByStart(c, i));
+                        self.center = node.left;
+                    } else {
+                        self.scan = node
+                            .value
+                            .by_end
+                            .first
+                            .map_or(Scan::Done, |i| Scan::ByEnd(c, i));
+                        self.center = node.right;
+                    }
+                }
+            }
+        }
+        // Then those starting after the point, and before the query's end
+        let idx = self.after?;
+        let (interval, id) = (&tree.sorted[idx].key.0, tree.sorted[idx].value);
+        match &self.end {
+            Some(end) if interval.start < *end => {
+                self.after = tree.sorted.successor(idx);
+                Some(tree.entry(id))
+            }
+            _ => {
+                self.after = None;
+                None
+            }
+        }
+    }
+}
+
+/// Local Error Type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error;
+
+#[test]
+fn test_interval() {
+    let a = Interval::new(1, 5).unwrap();
+    assert!(a.contains(&1) && a.contains(&4) && !a.contains(&5));
+    assert!(a.overlaps(&(4..9).into()));
+    assert!(!a.overlaps(&(5..9).into()));
+    assert!(!a.overlaps(&(3..3).into()));
+    assert_eq!(Interval::new(2, 1), Err(Error));
+
+    // Closed index-ranges include their maximum, unless it leaves no room for the exclusive end
+    let r = Interval::try_from(Range { min: 7, max: 10 }).unwrap();
+    assert!(r.contains(&10) && !r.contains(&11));
+    let r = Interval::try_from(Range {
+        min: 0,
+        max: usize::MAX,
+    });
+    assert_eq!(r, Err(Error));
+}
+
+#[test]
+fn test_interval_tree() {
+    // A day's bookings, in minutes
+    let mut tree = IntervalTree::new();
+    let bookings = [
+        (540, 600, "standup"),
+        (600, 660, "review"),
+        (630, 720, "interview"),
+        (720, 780, "lunch"),
+        (540, 1020, "on-call"),
+        (900, 960, "planning"),
+        (600, 660, "review-2"),
+    ];
+    for &(start, end, name) in bookings.iter() {
+        tree.insert(Interval::new(start, end).unwrap(), name);
+    }
+    assert!(tree.check_invariants());
+    assert_eq!(tree.len(), 7);
+
+    // Sorted names of the intervals overlapping a point, or a range
+    type Tree = IntervalTree<i32, &'static str>;
+    fn names<'t>(
+        found: impl Iterator<Item = (&'t Interval<i32>, &'t &'static str)>,
+    ) -> Vec<&'static str> {
+        let mut v: Vec<_> = found.map(|(_, &name)| name).collect();
+        v.sort_unstable();
+        v
+    }
+    let at = |tree: &Tree, p| names(tree.query_point(p));
+    let during = |tree: &Tree, r: std::ops::Range<i32>| names(tree.query(r.into()));
+    assert_eq!(at(&tree, 540), ["on-call", "standup"]);
+    assert_eq!(at(&tree, 600), ["on-call", "review", "review-2"]);
+    assert_eq!(
+        at(&tree, 645),
+        ["interview", "on-call", "review", "review-2"]
+    );
+    assert_eq!(at(&tree, 1020), Vec::<&str>::new());
+
+    assert_eq!(
+        during(&tree, 700..910),
+        ["interview", "lunch", "on-call", "planning"]
+    );
+    assert_eq!(during(&tree, 0..540), Vec::<&str>::new());
+    assert_eq!(during(&tree, 700..700), Vec::<&str>::new());
+
+    assert!(tree.find_overlap(&(1000..1100).into()).is_some());
+    assert!(tree.find_overlap(&(1020..1100).into()).is_none());
+
+    // Removal takes the earliest-inserted of equal intervals
+    assert_eq!(tree.remove(&(600..660).into()), Some("review"));
+    assert_eq!(tree.remove(&(540..1020).into()), Some("on-call"));
+    assert_eq!(tree.remove(&(540..1020).into()), None);
+    assert!(tree.check_invariants());
+    assert_eq!(at(&tree, 645), ["interview", "review-2"]);
+    assert!(tree.find_overlap(&(1000..1100).into()).is_none());
+
+    // Empty intervals are stored, but never overlap, nor mislead the search for those which do
+    let mut tree = IntervalTree::new();
+    tree.insert((7..9).into(), "real");
+    tree.insert((6..6).into(), "empty");
+    tree.insert((10..12).into(), "later");
+    assert!(tree.check_invariants());
+    let found = tree.find_overlap(&(5..8).into());
+    assert_eq!(found, Some((&(7..9).into(), &"real")));
+    assert_eq!(tree.query_point(6).count(), 0);
+}
+
+#[test]
+fn test_interval_tree_random() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(45);
+    let mut tree = IntervalTree::new();
+    let mut all = Vec::new();
+    for k in 0..500 {
+        let start = rng.gen_range(0..1000);
+        let interval = Interval::new(start, start + rng.gen_range(0..50)).unwrap();
+        tree.insert(interval, k);
+        all.push((interval, k));
+    }
+    // Compare against brute force, after some removals, and again after most, forcing a rebuild
+    let sorted = |found: Vec<i32>| {
+        let mut found = found;
+        found.sort_unstable();
+        found
+    };
+    for &removals in [150, 300].iter() {
+        for _ in 0..removals {
+            // Removal takes the earliest-inserted of any equal intervals, as does `position`
+            let (interval, _) = all[rng.gen_range(0..all.len())];
+            let pos = all.iter().position(|(i, _)| *i == interval).unwrap();
+            assert_eq!(tree.remove(&interval), Some(all.remove(pos).1));
+        }
+        assert!(tree.check_invariants());
+        assert_eq!(tree.len(), all.len());
+
+        for _ in 0..100 {
+            let start = rng.gen_range(0..1050);
+            let query = Interval::new(start, start + rng.gen_range(0..30)).unwrap();
+            let found = sorted(tree.query(query).map(|(_, &k)| k).collect());
+            let expected: Vec<i32> = all
+                .iter()
+                .filter(|(i, _)| i.overlaps(&query))
+                .map(|&(_, k)| k)
+                .collect();
+            assert_eq!(found, expected);
+            assert_eq!(tree.find_overlap(&query).is_some(), !expected.is_empty());
+
+            let found = sorted(tree.query_point(start).map(|(_, &k)| k).collect());
+            let expected: Vec<i32> = all
+                .iter()
+                .filter(|(i, _)| i.contains(&start))
+                .map(|&(_, k)| k)
+                .collect();
+            assert_eq!(found, expected);
+        }
+    }
+
+    // Nested intervals, inserted in start-order, are moved up through every rotation
+    let mut tree = IntervalTree::new();
+    for k in 0..200 {
+        tree.insert(Interval::new(k, 400 - k).unwrap(), k);
+    }
+    assert!(tree.check_invariants());
+    assert_eq!(tree.query_point(150).count(), 151);
+    assert_eq!(tree.query((399..500).into()).count(), 1);
+}
@@ -0,0 +1,377 @@
+//!
+//! # Lowest Common Ancestor Index
+//!
+//! Pre-processes a fixed rooted tree to answer ancestor queries without walking it:
+//! lowest common ancestors, distances between nodes, and `k`th ancestors.
+//! Compare [first_common_ancestor](crate::chapter_04::p08_first_common_ancestor::first_common_ancestor),
+//! which walks the entire tree for each query.
+//!
+//! Lowest common ancestors use an Euler tour of the tree, plus a sparse table of depth-minima over it.
+//! Construction takes O(n log n) time and space, after which each LCA or distance query takes O(1) time.
+//! Each `k`th-ancestor query is a binary search among the nodes at the ancestor's depth, in O(log n) time.
+//!
+//! [LcaIndex] operates on general rooted trees, with nodes numbered `0..n`.
+//! [BinaryTreeLca] wraps it for use with pointer-based [BinaryTree]s.
+//!
+
+use std::collections::HashMap;
+
+// Local Imports
+use crate::binary_tree::{BinaryTree, Node, NodePtr};
+use crate::tree_view::TreeView;
+
+///
+/// # Lowest Common Ancestor Index
+///
+/// Built from a tree's parent-array, in which node `i`'s parent is `parents[i]`, and the root's is `None`.
+/// Queries panic on node numbers out of range, as does slice-indexing.
+///
+#[derive(Debug, Clone)]
+pub struct LcaIndex {
+    /// Parent of each node
+    parent: Vec<Option<usize>>,
+    /// Depth of each node. Zero for the root.
+    depth: Vec<usize>,
+    /// Pre-order entry time of each node
+    tin: Vec<usize>,
+    /// Exit time of each node, one past the largest entry time in its sub-tree
+    tout: Vec<usize>,
+    /// Position of each node's first appearance in the Euler tour
+    first: Vec<usize>,
+    /// Sparse table over the Euler tour.
+    /// Entry `[j][i]` is the shallowest node among tour positions `i..i + 2^j`.
+    sparse: Vec<Vec<usize>>,
+    /// Nodes at each depth, in order of entry time
+    by_depth: Vec<Vec<usize>>,
+}
+impl LcaIndex {
+    /// Build an index from array `parents`.
+    /// Returns an `Err` unless exactly one node is parentless, all parents are in range,
+    /// and every node descends from the root, i.e. there are no cycles.
+    pub fn from_parents(parents: &[Option<usize>]) -> Result<Self, Error> {
+        let n = parents.len();
+        let mut children = vec![Vec::new(); n];
+        let mut roots = Vec::new();
+        for (node, parent) in parents.iter().enumerate() {
+            match *parent {
+                None => roots.push(node),
+                Some(p) if p < n => children[p].push(node),
+                Some(_) => return Err(Error), // Parent out of range
+            }
+        }
+        let root = match roots[..] {
+            [root] => root,
+            _ => return Err(Error), // No root, or several
+        };
+
+        // Iterative depth-first walk, recording the Euler tour: each node on entry,
+        // and again after returning from each of its children.
+        let mut depth = vec![0; n];
+        let mut tin = vec![0; n];
+        let mut tout = vec![0; n];
+        let mut first = vec![0; n];
+        let mut euler = Vec::with_capacity(2 * n - 1);
+        let mut by_depth: Vec<Vec<usize>> = vec![vec![root]];
+        let mut timer = 1;
+        euler.push(root);
+        let mut stack = vec![(root, 0)];
+        while let Some(&mut (node, ref mut next)) = stack.last_mut() {
+            if let Some(&child) = children[node].get(*next) {
+                *next += 1;
+                depth[child] = depth[node] + 1;
+                tin[child] = timer;
+                timer += 1;
+                first[child] = euler.len();
+                euler.push(child);
+                if by_depth.len() <= depth[child] {
+                    by_depth.push(Vec::new());
+                }
+                by_depth[depth[child]].push(child);
+                stack.push((child, 0));
+            } else {
+                tout[node] = timer;
+                stack.pop();
+                if let Some(&(parent, _)) = stack.last() {
+                    euler.push(parent);
+                }
+            }
+        }
+        if timer != n {
+            return Err(Error); // Some nodes are unreachable from the root, i.e. lie on cycles
+        }
+
+        // Build the sparse table, each level from the one below
+        let shallower = |a: usize, b: usize| if depth[a] <= depth[b] { a } else { b };
+        let mut sparse = vec![euler];
+        let mut width = 1;
+        while 2 * width <= sparse[0].len() {
+            let prev = sparse.last().unwrap();
+            let level = (0..prev.len() - width)
+                .map(|i| shallower(prev[i], prev[i + width]))
+                .collect();
+            sparse.push(level);
+            width *= 2;
+        }
+
+        Ok(Self {
+            parent: parents.to_vec(),
+            depth,
+            tin,
+            tout,
+            first,
+            sparse,
+            by_depth,
+        })
+    }
+    /// Get the number of nodes
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+    /// Boolean indication of whether the tree is empty. Always false, as every index has a root.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+    /// Get the root node
+    pub fn root(&self) -> usize {
+        self.by_depth[0][0]
+    }
+    /// Get the parent of `node`, or `None` for the root
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        self.parent[node]
+    }
+    /// Get the depth of `node`. Zero for the root.
+    pub fn depth(&self, node: usize) -> usize {
+        self.depth[node]
+    }
+    /// Boolean indication of whether `a` is an ancestor of `b`.
+    /// Nodes count as their own ancestors.
+    pub fn is_ancestor(&self, a: usize, b: usize) -> bool {
+        self.tin[a] <= self.tin[b] && self.tin[b] < self.tout[a]
+    }
+    /// Get the lowest common ancestor of `a` and `b`: the deepest node which is an ancestor of both
+    pub fn lca(&self, a: usize, b: usize) -> usize {
+        let (mut lo, mut hi) = (self.first[a], self.first[b]);
+        if lo > hi {
+            std::mem::swap(&mut lo, &mut hi);
+        }
+        // Cover tour positions `lo..=hi` with two overlapping power-of-two windows
+        let level = (usize::BITS - 1 - (hi - lo + 1).leading_zeros()) as usize;
+        let x = self.sparse[level][lo];
+        let y = self.sparse[level][hi + 1 - (1 << level)];
+        if self.depth[x] <= self.depth[y] {
+            x
+        } else {
+            y
+        }
+    }
+    /// Get the number of edges on the path between `a` and `b`
+    pub fn distance(&self, a: usize, b: usize) -> usize {
+        self.depth[a] + self.depth[b] - 2 * self.depth[self.lca(a, b)]
+    }
+    /// Get the ancestor `k` levels above `node`, or `None` if `node` has fewer than `k` ancestors.
+    /// The zeroth ancestor is `node` itself.
+    pub fn kth_ancestor(&self, node: usize, k: usize) -> Option<usize> {
+        let level = &self.by_depth[self.depth[node].checked_sub(k)?];
+        // The ancestor is the last node at its depth entered before `node`
+        let pos = level.partition_point(|&n| self.tin[n] <= self.tin[node]);
+        Some(level[pos - 1])
+    }
+}
+
+///
+/// # Binary Tree LCA Index
+///
+/// An [LcaIndex] over a pointer-based [BinaryTree], with queries by [NodePtr].
+/// Holds a handle to each node, keeping them alive and their addresses stable.
+/// Changes to the tree's shape after construction are not reflected in query results.
+///
+#[derive(Debug)]
+pub struct BinaryTreeLca<T> {
+    index: LcaIndex,
+    /// Each node, in pre-order, by its number in `index`
+    nodes: Vec<NodePtr<T>>,
+    /// Node numbers, by address
+    ids: HashMap<*const Node<T>, usize>,
+}
+impl<T> BinaryTreeLca<T> {
+    /// Build an index over `tree`. Returns an `Err` if the tree is empty.
+    pub fn new(tree: &BinaryTree<T>) -> Result<Self, Error> {
+        let nodes: Vec<NodePtr<T>> = tree.iter_preorder().collect();
+        let ids: HashMap<*const Node<T>, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(id, ptr)| (ptr.as_ptr() as *const _, id))
+            .collect();
+        let mut parents = vec![None; nodes.len()];
+        for (id, ptr) in nodes.iter().enumerate() {
+            let node = ptr.borrow();
+            for child in node.left.iter().chain(node.right.iter()) {
+                parents[ids[&(child.as_ptr() as *const _)]] = Some(id);
+            }
+        }
+        Ok(Self {
+            index: LcaIndex::from_parents(&parents)?,
+            nodes,
+            ids,
+        })
+    }
+    /// Get the underlying [LcaIndex]
+    pub fn index(&self) -> &LcaIndex {
+        &self.index
+    }
+    /// Get the number of node `ptr` in [BinaryTreeLca::index], if it is in the tree
+    pub fn id(&self, ptr: &NodePtr<T>) -> Option<usize> {
+        self.ids.get(&(ptr.as_ptr() as *const _)).copied()
+    }
+    /// Get the node numbered `id`
+    pub fn node(&self, id: usize) -> &NodePtr<T> {
+        &self.nodes[id]
+    }
+    /// Get the lowest common ancestor of `p` and `q`, if both are in the tree
+    pub fn lca(&self, p: &NodePtr<T>, q: &NodePtr<T>) -> Option<NodePtr<T>> {
+        let lca = self.index.lca(self.id(p)?, self.id(q)?);
+        Some(self.nodes[lca].clone())
+    }
+    /// Get the number of edges on the path between `p` and `q`, if both are in the tree
+    pub fn distance(&self, p: &NodePtr<T>, q: &NodePtr<T>) -> Option<usize> {
+        Some(self.index.distance(self.id(p)?, self.id(q)?))
+    }
+    /// Get the ancestor `k` levels above `ptr`, if `ptr` is in the tree and has at least `k` ancestors
+    pub fn kth_ancestor(&self, ptr: &NodePtr<T>, k: usize) -> Option<NodePtr<T>> {
+        let ancestor = self.index.kth_ancestor(self.id(ptr)?, k)?;
+        Some(self.nodes[ancestor].clone())
+    }
+}
+
+/// Local Error Type
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error;
+
+#[test]
+fn test_lca_index() {
+    //        0
+    //      / | \
+    //     1  2  3
+    //    / \     \
+    //   4   5     6
+    //       |
+    //       7
+    let parents = [
+        None,
+        Some(0),
+        Some(0),
+        Some(0),
+        Some(1),
+        Some(1),
+        Some(3),
+        Some(5),
+    ];
+    let index = LcaIndex::from_parents(&parents).unwrap();
+    assert_eq!(index.len(), 8);
+    assert_eq!(index.root(), 0);
+    assert_eq!(index.depth(7), 3);
+    assert_eq!(index.lca(4, 7), 1);
+    assert_eq!(index.lca(7, 4), 1);
+    assert_eq!(index.lca(7, 6), 0);
+    assert_eq!(index.lca(5, 7), 5);
+    assert_eq!(index.lca(2, 2), 2);
+    assert_eq!(index.distance(4, 7), 3);
+    assert_eq!(index.distance(7, 6), 5);
+    assert_eq!(index.distance(3, 3), 0);
+    assert_eq!(index.kth_ancestor(7, 0), Some(7));
+    assert_eq!(index.kth_ancestor(7, 2), Some(1));
+    assert_eq!(index.kth_ancestor(7, 3), Some(0));
+    assert_eq!(index.kth_ancestor(7, 4), None);
+    assert_eq!(index.kth_ancestor(6, 1), Some(3));
+    assert!(index.is_ancestor(1, 7) && index.is_ancestor(7, 7) && !index.is_ancestor(2, 7));
+
+    // Invalid parent-arrays
+    assert_eq!(LcaIndex::from_parents(&[]).unwrap_err(), Error);
+    assert_eq!(LcaIndex::from_parents(&[None, None]).unwrap_err(), Error);
+    assert_eq!(LcaIndex::from_parents(&[None, Some(5)]).unwrap_err(), Error);
+    assert_eq!(
+        LcaIndex::from_parents(&[None, Some(2), Some(1)]).unwrap_err(),
+        Error
+    );
+    let single = LcaIndex::from_parents(&[None]).unwrap();
+    assert_eq!(single.lca(0, 0), 0);
+    assert_eq!(single.kth_ancestor(0, 1), None);
+}
+
+#[test]
+fn test_lca_random() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    // Compare against walking parent-links, on random trees of various shapes
+    let mut rng = StdRng::seed_from_u64(49);
+    for n in [1, 2, 3, 10, 100, 500] {
+        // Number nodes in random order, each with a random parent among those before it
+        let mut order: Vec<usize> = (0..n).collect();
+        for i in (1..n).rev() {
+            order.swap(i, rng.gen_range(0..=i));
+        }
+        let mut parents = vec![None; n];
+        for i in 1..n {
+            // Bias towards recent nodes, for deeper trees
+            let lo = i.saturating_sub(3);
+            let p = if rng.gen_bool(0.8) {
+                rng.gen_range(lo..i)
+            } else {
+                rng.gen_range(0..i)
+            };
+            parents[order[i]] = Some(order[p]);
+        }
+        let index = LcaIndex::from_parents(&parents).unwrap();
+        let ancestors = |mut node: usize| {
+            let mut path = vec![node];
+            while let Some(p) = parents[node] {
+                path.push(p);
+                node = p;
+            }
+            path
+        };
+        for _ in 0..200 {
+            let (a, b) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            let (pa, pb) = (ancestors(a), ancestors(b));
+            let lca = *pa.iter().find(|x| pb.contains(x)).unwrap();
+            assert_eq!(index.lca(a, b), lca);
+            let distance = pa.iter().position(|&x| x == lca).unwrap()
+                + pb.iter().position(|&x| x == lca).unwrap();
+            assert_eq!(index.distance(a, b), distance);
+            let k = rng.gen_range(0..=pa.len());
+            assert_eq!(index.kth_ancestor(a, k), pa.get(k).copied());
+            assert_eq!(index.is_ancestor(b, a), pa.contains(&b));
+        }
+    }
+}
+
+#[test]
+fn test_binary_tree_lca() {
+    use crate::chapter_04::p08_first_common_ancestor::first_common_ancestor;
+
+    let tree = BinaryTree::deserialize("[1,2,3,4,5,null,6,7,null,8,9,null,null,10]").unwrap();
+    let nodes: Vec<NodePtr<isize>> = tree.iter_preorder().collect();
+    let lca = BinaryTreeLca::new(&tree).unwrap();
+    for p in nodes.iter() {
+        // Nodes are their own ancestors. (`first_common_ancestor` finds none for `p == q`.)
+        assert_eq!(lca.lca(p, p).as_ref(), Some(p));
+        for q in nodes.iter().filter(|&q| q != p) {
+            assert_eq!(lca.lca(p, q), first_common_ancestor(&tree, p, q));
+        }
+    }
+    let find = |data| nodes.iter().find(|p| p.borrow().data == data).unwrap();
+    assert_eq!(lca.distance(find(10), find(9)), Some(5));
+    assert_eq!(lca.distance(find(10), find(6)), Some(6));
+    assert_eq!(lca.kth_ancestor(find(10), 3).as_ref(), Some(find(2)));
+    assert_eq!(lca.kth_ancestor(find(10), 5), None);
+    assert_eq!(lca.node(lca.id(find(8)).unwrap()), find(8));
+
+    // Nodes from elsewhere are not found
+    let other = BinaryTree::deserialize("[1]").unwrap();
+    let stranger = other.head.as_ref().unwrap();
+    assert_eq!(lca.lca(stranger, find(1)), None);
+    assert_eq!(lca.distance(find(1), stranger), None);
+
+    let empty: BinaryTree<isize> = BinaryTree::default();
+    assert_eq!(BinaryTreeLca::new(&empty).unwrap_err(), Error);
+}
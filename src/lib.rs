@@ -17,15 +17,27 @@ pub mod chapter_10;
 pub mod chapter_16;
 pub mod chapter_17;
 
+pub mod arena_graph;
+pub mod arena_tree;
 pub mod binary_search_tree;
 pub mod binary_tree;
+pub mod binomial_heap;
 pub mod dijkstra;
+pub mod disk_btree;
+pub mod external_sort;
 pub mod heap;
+pub mod interval_tree;
+pub mod lca;
+pub mod pairing_heap;
+pub mod persistent_bst;
 pub mod ptr;
 pub mod ptr_list;
 pub mod sort;
+pub mod sort_bench;
+pub mod splay_tree;
+pub mod treap;
+pub mod tree_view;
 pub mod trie;
-pub mod arena_graph;
 
 /// Library-Level Do-Nothing Test
 #[test]
@@ -0,0 +1,360 @@
+//!
+//! # Pairing Heap
+//!
+//! Mergeable heap with O(1) `push` and `meld`, and amortized O(log n) `pop` and `decrease_key`.
+//!
+//! Nodes are stored in a "left-child, right-sibling" multi-way tree.
+//! Child and sibling links are strong [Rc] pointers, while each node's back-link to its
+//! predecessor (its parent if it is the leftmost child, or its left sibling otherwise) is a [Weak] one.
+//!
+
+use std::{
+    cell::{Ref, RefCell},
+    cmp::Ordering,
+    rc::{Rc, Weak},
+};
+
+// Local Imports
+use crate::heap::{Compare, MinOrder, Owner, PriorityQueue};
+
+/// Alias for the shared pointer-type to a [Node]
+type NodePtr<T> = Rc<RefCell<Node<T>>>;
+
+/// Pairing Heap Node
+#[derive(Debug)]
+struct Node<T> {
+    data: T,
+    /// Identity of the heap into which the element was pushed
+    owner: Rc<Owner>,
+    child: Option<NodePtr<T>>,
+    sibling: Option<NodePtr<T>>,
+    prev: Option<Weak<RefCell<Node<T>>>>,
+}
+impl<T> Node<T> {
+    /// Create a new, un-linked [Node] pointer, pushed into the heap identified by `owner`
+    fn new(data: T, owner: &Rc<Owner>) -> NodePtr<T> {
+        Rc::new(RefCell::new(Self {
+            data,
+            owner: Rc::clone(owner),
+            child: None,
+            sibling: None,
+            prev: None,
+        }))
+    }
+}
+
+///
+/// # Element Handle
+///
+/// Returned by [PairingHeap::push], and used to address an element for [PairingHeap::decrease_key].
+/// Holds a weak reference, which becomes invalid once the element is popped.
+///
+#[derive(Debug)]
+pub struct Handle<T>(Weak<RefCell<Node<T>>>);
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self(Weak::clone(&self.0))
+    }
+}
+
+///
+/// # Pairing Heap
+///
+/// Ordering is set by comparator-type `C`, which defaults to [MinOrder].
+///
+#[derive(Debug)]
+pub struct PairingHeap<T, C = MinOrder> {
+    root: Option<NodePtr<T>>,
+    len: usize,
+    cmp: C,
+    owner: Rc<Owner>,
+}
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::with_comparator(MinOrder)
+    }
+}
+impl<T: Ord> PairingHeap<T> {
+    /// Create a new, empty min-heap. Also available via [Default].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl<T, C: Compare<T>> PairingHeap<T, C> {
+    /// Create a new, empty heap ordered by comparator `cmp`
+    pub fn with_comparator(cmp: C) -> Self {
+        Self {
+            root: None,
+            len: 0,
+            cmp,
+            owner: Owner::new(),
+        }
+    }
+    /// Push an element onto the heap. Returns a [Handle] to it.
+    pub fn push(&mut self, t: T) -> Handle<T> {
+        let node = Node::new(t, &self.owner);
+        let handle = Handle(Rc::downgrade(&node));
+        self.root = Some(match self.root.take() {
+            None => node,
+            Some(root) => self.link(root, node),
+        });
+        self.len += 1;
+        handle
+    }
+    /// Pop the top element from the heap. Returns `None` if empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        self.len -= 1;
+
+        // Detach each of the root's children into a list of sub-heaps
+        let mut subheaps = Vec::new();
+        let mut child = root.borrow_mut().child.take();
+        while let Some(c) = child {
+            child = {
+                let mut node = c.borrow_mut();
+                node.prev = None;
+                node.sibling.take()
+            };
+            subheaps.push(c);
+        }
+
+        // And combine them in two passes: pair-wise left-to-right, then accumulating right-to-left
+        let mut pairs = Vec::with_capacity(subheaps.len() / 2 + 1);
+        let mut iter = subheaps.into_iter();
+        while let Some(a) = iter.next() {
+            pairs.push(match iter.next() {
+                Some(b) => self.link(a, b),
+                None => a,
+            });
+        }
+        self.root = pairs
+            .into_iter()
+            .rev()
+            .reduce(|acc, next| self.link(acc, next));
+
+        // The old root is now referenced only by weak handles. Unwrap and return its data.
+        match Rc::try_unwrap(root) {
+            Ok(cell) => Some(cell.into_inner().data),
+            Err(_) => unreachable!("Internal Error: popped node still shared"),
+        }
+    }
+    /// Get a reference to the top element, without removing it. Returns `None` if empty.
+    pub fn peek(&self) -> Option<Ref<'_, T>> {
+        self.root
+            .as_ref()
+            .map(|root| Ref::map(root.borrow(), |node| &node.data))
+    }
+    /// Get the number of elements in the heap
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Boolean indication of whether the heap is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Merge all elements of `other` into this heap, in O(1) time.
+    /// [Handle]s into `other` remain valid, and now address elements of `self`.
+    pub fn meld(&mut self, mut other: Self) {
+        Owner::absorb(&self.owner, &other.owner);
+        self.len += other.len;
+        other.len = 0;
+        self.root = match (self.root.take(), other.root.take()) {
+            (Some(a), Some(b)) => Some(self.link(a, b)),
+            (a, b) => a.or(b),
+        };
+    }
+    /// Raise the priority of the element at `handle`, replacing its value with `t`.
+    ///
+    /// Returns an [Error] if `handle` is no longer valid (i.e. its element has been popped),
+    /// if it was issued by another heap (other than one since melded into this one),
+    /// or if `t` would *lower* the element's priority.
+    pub fn decrease_key(&mut self, handle: &Handle<T>, t: T) -> Result<(), Error> {
+        let node = handle.0.upgrade().ok_or(Error)?;
+        if !Owner::is_owned_by(&node.borrow().owner, &self.owner) {
+            return Err(Error);
+        }
+        if self.cmp.compare(&t, &node.borrow().data) == Ordering::Greater {
+            return Err(Error);
+        }
+        node.borrow_mut().data = t;
+
+        // If `node` is the root, the heap property holds. Done.
+        let prev = match node.borrow().prev.as_ref() {
+            None => return Ok(()),
+            Some(p) => p.upgrade().ok_or(Error)?,
+        };
+
+        // Otherwise cut its sub-tree out from its predecessor
+        let sibling = {
+            let mut n = node.borrow_mut();
+            n.prev = None;
+            n.sibling.take()
+        };
+        if let Some(s) = sibling.as_ref() {
+            s.borrow_mut().prev = Some(Rc::downgrade(&prev));
+        }
+        {
+            let mut p = prev.borrow_mut();
+            let is_parent = matches!(p.child.as_ref(), Some(c) if Rc::ptr_eq(c, &node));
+            if is_parent {
+                p.child = sibling;
+            } else {
+                p.sibling = sibling;
+            }
+        }
+
+        // And link it back in with the root
+        let root = self.root.take().ok_or(Error)?;
+        self.root = Some(self.link(root, node));
+        Ok(())
+    }
+    /// Link two root-nodes `a` and `b`.
+    /// The lower-priority of the two becomes the leftmost child of the other, which is returned.
+    fn link(&self, a: NodePtr<T>, b: NodePtr<T>) -> NodePtr<T> {
+        let (winner, loser) =
+            if self.cmp.compare(&b.borrow().data, &a.borrow().data) == Ordering::Less {
+                (b, a)
+            } else {
+                (a, b)
+            };
+        {
+            let mut w = winner.borrow_mut();
+            let mut l = loser.borrow_mut();
+            if let Some(c) = w.child.as_ref() {
+                c.borrow_mut().prev = Some(Rc::downgrade(&loser));
+            }
+            l.sibling = w.child.take();
+            l.prev = Some(Rc::downgrade(&winner));
+        }
+        winner.borrow_mut().child = Some(loser);
+        winner
+    }
+}
+
+impl<T, C> Drop for PairingHeap<T, C> {
+    /// Iteratively tear down the tree.
+    /// The default recursive drop can overflow the stack on long sibling-chains.
+    fn drop(&mut self) {
+        let mut stack: Vec<NodePtr<T>> = self.root.take().into_iter().collect();
+        while let Some(ptr) = stack.pop() {
+            let mut node = ptr.borrow_mut();
+            stack.extend(node.child.take());
+            stack.extend(node.sibling.take());
+        }
+    }
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T> for PairingHeap<T, C> {
+    type Peek<'a>
+        = Ref<'a, T>
+    where
+        Self: 'a;
+
+    fn push(&mut self, t: T) {
+        PairingHeap::push(self, t);
+    }
+    fn pop(&mut self) -> Option<T> {
+        PairingHeap::pop(self)
+    }
+    fn peek(&self) -> Option<Ref<'_, T>> {
+        PairingHeap::peek(self)
+    }
+    fn len(&self) -> usize {
+        self.len
+    }
+    fn meld(&mut self, other: Self) {
+        PairingHeap::meld(self, other)
+    }
+}
+
+/// Local Error Type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error;
+
+#[test]
+fn test_pairing_heap() {
+    let mut heap = PairingHeap::new();
+    for k in [10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5] {
+        heap.push(k);
+    }
+    assert_eq!(heap.len(), 11);
+    assert_eq!(*heap.peek().unwrap(), 0);
+
+    let mut popped = Vec::new();
+    while let Some(k) = heap.pop() {
+        popped.push(k);
+    }
+    assert_eq!(popped, (0..=10).collect::<Vec<_>>());
+    assert!(heap.is_empty());
+    assert!(heap.peek().is_none());
+}
+
+#[test]
+fn test_pairing_meld() {
+    use crate::heap::drain_sorted;
+
+    let mut a = PairingHeap::new();
+    let mut b = PairingHeap::new();
+    for k in 0..50 {
+        a.push(2 * k);
+        b.push(2 * k + 1);
+    }
+    a.meld(b);
+    assert_eq!(a.len(), 100);
+    assert_eq!(drain_sorted(&mut a), (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_pairing_decrease_key() -> Result<(), Error> {
+    let mut heap = PairingHeap::new();
+    let handles: Vec<_> = (0..20).map(|k| heap.push(k * 10)).collect();
+
+    // Move a few elements toward the top
+    heap.decrease_key(&handles[15], 5)?;
+    heap.decrease_key(&handles[7], -1)?;
+    assert_eq!(*heap.peek().unwrap(), -1);
+    // Lowering priority is an error
+    assert_eq!(heap.decrease_key(&handles[3], 100), Err(Error));
+
+    assert_eq!(heap.pop(), Some(-1));
+    assert_eq!(heap.pop(), Some(0));
+    assert_eq!(heap.pop(), Some(5));
+    assert_eq!(heap.pop(), Some(10));
+
+    // Popped elements' handles are invalid
+    assert_eq!(heap.decrease_key(&handles[0], -5), Err(Error));
+    assert_eq!(heap.len(), 16);
+
+    // Handles survive a `meld`
+    let mut other = PairingHeap::new();
+    let h = other.push(1000);
+    heap.meld(other);
+    heap.decrease_key(&h, 1)?;
+    assert_eq!(heap.pop(), Some(1));
+
+    // Handles from other heaps are rejected, including after either is melded elsewhere
+    let mut other = PairingHeap::new();
+    let h = other.push(1000);
+    assert_eq!(heap.decrease_key(&h, 0), Err(Error));
+    let mut third = PairingHeap::new();
+    third.meld(other);
+    assert_eq!(heap.decrease_key(&h, 0), Err(Error));
+    third.meld(heap);
+    assert_eq!(third.decrease_key(&handles[19], 0), Ok(()));
+    assert_eq!(third.decrease_key(&h, -1), Ok(()));
+    assert_eq!(third.pop(), Some(-1));
+    Ok(())
+}
+
+#[test]
+fn test_pairing_max() {
+    use crate::heap::MaxOrder;
+
+    let mut heap = PairingHeap::with_comparator(MaxOrder);
+    for k in 0..1_000 {
+        heap.push(k);
+    }
+    for k in (0..1_000).rev() {
+        assert_eq!(heap.pop(), Some(k));
+    }
+}
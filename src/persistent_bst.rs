@@ -0,0 +1,606 @@
+//!
+//! # Persistent Binary Search Tree
+//!
+//! An immutable, AVL-balanced ordered map. Updates return a new version of the map,
+//! leaving the original intact. Versions share every sub-tree which an update does not touch,
+//! via reference-counted [Rc] links, so that each update allocates only the O(log n) nodes
+//! along its search path, and snapshots are a single reference-count increment.
+//!
+//! Queries mirror those of [crate::binary_search_tree::BstMap],
+//! returning references to keys and values in place of node-indices.
+//!
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
+use std::rc::Rc;
+
+// Alias for a (shared, optional) link to a [Node]
+type Link<K, V> = Option<Rc<Node<K, V>>>;
+
+///
+/// # Persistent Tree Node
+///
+/// Immutable once created. Caches the height and size of its sub-tree.
+///
+#[derive(Debug)]
+pub struct Node<K, V> {
+    pub key: K,
+    pub value: V,
+    height: usize,
+    size: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+impl<K, V> Node<K, V> {
+    /// Create a new shared [Node], computing its height and size from its children
+    fn new(key: K, value: V, left: Link<K, V>, right: Link<K, V>) -> Rc<Self> {
+        Rc::new(Self {
+            key,
+            value,
+            height: 1 + height(&left).max(height(&right)),
+            size: 1 + size(&left) + size(&right),
+            left,
+            right,
+        })
+    }
+}
+/// Get the height of optional sub-tree `link`
+fn height<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |n| n.height)
+}
+/// Get the size of optional sub-tree `link`
+fn size<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |n| n.size)
+}
+
+///
+/// # Persistent Map
+///
+/// Cloning is cheap, and is the same as [PersistentMap::snapshot].
+///
+#[derive(Debug)]
+pub struct PersistentMap<K, V> {
+    head: Link<K, V>,
+}
+impl<K, V> Default for PersistentMap<K, V> {
+    fn default() -> Self {
+        Self { head: None }
+    }
+}
+impl<K, V> Clone for PersistentMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            head: self.head.clone(),
+        }
+    }
+}
+impl<K: Ord + Clone, V: Clone> PersistentMap<K, V> {
+    /// Create a new, empty map. Also available via [Default].
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Get a new version of the map, with `value` at `key`.
+    /// Replaces any existing value at `key`.
+    pub fn insert(&self, key: K, value: V) -> Self {
+        Self {
+            head: Some(Self::insert_helper(&self.head, key, value)),
+        }
+    }
+    /// Get a new version of the map, with `key` removed.
+    /// If `key` is not present, the new version shares the entirety of this one.
+    pub fn remove<Q>(&self, key: &Q) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match Self::remove_helper(&self.head, key) {
+            Some(head) => Self { head },
+            None => self.clone(),
+        }
+    }
+    /// Recursive helper for `insert`. Copies the path from `link` to the new node.
+    fn insert_helper(link: &Link<K, V>, key: K, value: V) -> Rc<Node<K, V>> {
+        let node = match link {
+            None => return Node::new(key, value, None, None),
+            Some(n) => n,
+        };
+        match key.cmp(&node.key) {
+            Ordering::Less => {
+                let left = Self::insert_helper(&node.left, key, value);
+                Self::balance(node, Some(left), node.right.clone())
+            }
+            Ordering::Greater => {
+                let right = Self::insert_helper(&node.right, key, value);
+                Self::balance(node, node.left.clone(), Some(right))
+            }
+            Ordering::Equal => Node::new(key, value, node.left.clone(), node.right.clone()),
+        }
+    }
+    /// Recursive helper for `remove`.
+    /// Returns the replacement for sub-tree `link`, or `None` if `key` is not present, and nothing changes.
+    fn remove_helper<Q>(link: &Link<K, V>, key: &Q) -> Option<Link<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let node = link.as_ref()?;
+        let replacement = match key.cmp(node.key.borrow()) {
+            Ordering::Less => {
+                let left = Self::remove_helper(&node.left, key)?;
+                Self::balance(node, left, node.right.clone())
+            }
+            Ordering::Greater => {
+                let right = Self::remove_helper(&node.right, key)?;
+                Self::balance(node, node.left.clone(), right)
+            }
+            Ordering::Equal => match (&node.left, &node.right) {
+                (None, child) | (child, None) => return Some(child.clone()),
+                (Some(_), Some(right)) => {
+                    // Replace `node` with its in-order successor, the minimum of its right sub-tree
+                    let (successor, right) = Self::remove_min(right);
+                    Self::rebalance(
+                        successor.key.clone(),
+                        successor.value.clone(),
+                        node.left.clone(),
+                        right,
+                    )
+                }
+            },
+        };
+        Some(Some(replacement))
+    }
+    /// Remove the minimum node of sub-tree `node`.
+    /// Returns the removed node, and the replacement sub-tree.
+    fn remove_min(node: &Rc<Node<K, V>>) -> (Rc<Node<K, V>>, Link<K, V>) {
+        match &node.left {
+            None => (node.clone(), node.right.clone()),
+            Some(left) => {
+                let (min, left) = Self::remove_min(left);
+                (min, Some(Self::balance(node, left, node.right.clone())))
+            }
+        }
+    }
+    /// Create a copy of `node`, with new children `left` and `right`, re-balancing if necessary
+    fn balance(node: &Node<K, V>, left: Link<K, V>, right: Link<K, V>) -> Rc<Node<K, V>> {
+        Self::rebalance(node.key.clone(), node.value.clone(), left, right)
+    }
+    /// Create a node from `key`, `value`, and children `left` and `right`,
+    /// whose heights may differ by at most two. Rotates if they differ by two.
+    fn rebalance(key: K, value: V, left: Link<K, V>, right: Link<K, V>) -> Rc<Node<K, V>> {
+        let (lh, rh) = (height(&left), height(&right));
+        if lh > rh + 1 {
+            let l = left.unwrap();
+            if height(&l.left) >= height(&l.right) {
+                // Single right rotation
+                let right = Node::new(key, value, l.right.clone(), right);
+                Self::balance(&l, l.left.clone(), Some(right))
+            } else {
+                // Left-right double rotation
+                let lr = l.right.as_ref().unwrap();
+                let left = Self::balance(&l, l.left.clone(), lr.left.clone());
+                let right = Node::new(key, value, lr.right.clone(), right);
+                Self::balance(lr, Some(left), Some(right))
+            }
+        } else if rh > lh + 1 {
+            let r = right.unwrap();
+            if height(&r.right) >= height(&r.left) {
+                // Single left rotation
+                let left = Node::new(key, value, left, r.left.clone());
+                Self::balance(&r, Some(left), r.right.clone())
+            } else {
+                // Right-left double rotation
+                let rl = r.left.as_ref().unwrap();
+                let left = Node::new(key, value, left, rl.left.clone());
+                let right = Self::balance(&r, rl.right.clone(), r.right.clone());
+                Self::balance(rl, Some(left), Some(right))
+            }
+        } else {
+            Node::new(key, value, left, right)
+        }
+    }
+}
+impl<K: Ord, V> PersistentMap<K, V> {
+    /// Get a reference to the value at `key`, if present
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).map(|node| &node.value)
+    }
+    /// Boolean indication of whether `key` is present
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+    /// Find the node with key `key`, if present
+    pub fn find<Q>(&self, key: &Q) -> Option<&Node<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut link = &self.head;
+        while let Some(node) = link {
+            link = match key.cmp(node.key.borrow()) {
+                Ordering::Equal => return Some(node),
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+            };
+        }
+        None
+    }
+    /// Get an in-order iterator over the entries with keys in `range`
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let mut iter = Iter {
+            front: Vec::new(),
+            back: Vec::new(),
+            remaining: self.count_range::<Q, _>((range.start_bound(), range.end_bound())),
+        };
+        // Push the paths to the first and last entries in range.
+        // Nodes are pushed only when they, and their right (respectively left) sub-trees, remain to be visited.
+        let mut link = &self.head;
+        while let Some(node) = link {
+            let key = node.key.borrow();
+            let above = match range.start_bound() {
+                Bound::Unbounded => true,
+                Bound::Included(start) => key >= start,
+                Bound::Excluded(start) => key > start,
+            };
+            if above {
+                iter.front.push(node);
+                link = &node.left;
+            } else {
+                link = &node.right;
+            }
+        }
+        let mut link = &self.head;
+        while let Some(node) = link {
+            let key = node.key.borrow();
+            let below = match range.end_bound() {
+                Bound::Unbounded => true,
+                Bound::Included(end) => key <= end,
+                Bound::Excluded(end) => key < end,
+            };
+            if below {
+                iter.back.push(node);
+                link = &node.right;
+            } else {
+                link = &node.left;
+            }
+        }
+        iter
+    }
+    /// Get the rank of `key`: the number of keys strictly less than it.
+    /// `key` need not be present.
+    pub fn rank<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.count_less(key, false)
+    }
+    /// Get the node with the `k`th smallest key, counting from zero.
+    /// Returns `None` if `k` is out of bounds.
+    pub fn select(&self, mut k: usize) -> Option<&Node<K, V>> {
+        let mut link = &self.head;
+        while let Some(node) = link {
+            let left = size(&node.left);
+            link = match k.cmp(&left) {
+                Ordering::Equal => return Some(node),
+                Ordering::Less => &node.left,
+                Ordering::Greater => {
+                    k -= left + 1;
+                    &node.right
+                }
+            };
+        }
+        None
+    }
+    /// Count the keys in `range`
+    pub fn count_range<Q, R>(&self, range: R) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let before = match range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Included(a) => self.count_less(a, false),
+            Bound::Excluded(a) => self.count_less(a, true),
+        };
+        let through = match range.end_bound() {
+            Bound::Unbounded => self.len(),
+            Bound::Included(b) => self.count_less(b, true),
+            Bound::Excluded(b) => self.count_less(b, false),
+        };
+        through.saturating_sub(before)
+    }
+    /// Count the keys less than `key`, or less than or equal to it if `inclusive`
+    fn count_less<Q>(&self, key: &Q, inclusive: bool) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut count = 0;
+        let mut link = &self.head;
+        while let Some(node) = link {
+            let counted = match node.key.borrow().cmp(key) {
+                Ordering::Less => true,
+                Ordering::Equal => inclusive,
+                Ordering::Greater => false,
+            };
+            link = if counted {
+                count += size(&node.left) + 1;
+                &node.right
+            } else {
+                &node.left
+            };
+        }
+        count
+    }
+    /// Check the map's ordering, balance, and cached heights and sizes
+    pub fn check_invariants(&self) -> bool {
+        Self::check_helper(&self.head, None, None).is_some()
+    }
+    /// Recursive helper for `check_invariants`.
+    /// Returns the height of sub-tree `link` if valid, with all keys between `min` and `max`.
+    fn check_helper(link: &Link<K, V>, min: Option<&K>, max: Option<&K>) -> Option<usize> {
+        let node = match link {
+            None => return Some(0),
+            Some(n) => n,
+        };
+        if min.is_some_and(|m| node.key <= *m) || max.is_some_and(|m| node.key >= *m) {
+            return None;
+        }
+        let lh = Self::check_helper(&node.left, min, Some(&node.key))?;
+        let rh = Self::check_helper(&node.right, Some(&node.key), max)?;
+        let ok = lh.abs_diff(rh) <= 1
+            && node.height == 1 + lh.max(rh)
+            && node.size == 1 + size(&node.left) + size(&node.right);
+        ok.then_some(node.height)
+    }
+}
+impl<K, V> PersistentMap<K, V> {
+    /// Get a snapshot of the current version. Equivalent to, and as cheap as, [Clone].
+    pub fn snapshot(&self) -> Self {
+        self.clone()
+    }
+    /// Boolean indication of whether `self` and `other` are the same version,
+    /// i.e. share the same root node
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+    /// Get the number of entries
+    pub fn len(&self) -> usize {
+        size(&self.head)
+    }
+    /// Boolean indication of whether the map is empty
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+    /// Get the entry with the minimum key, if any
+    pub fn min(&self) -> Option<&Node<K, V>> {
+        let mut node = self.head.as_ref()?;
+        while let Some(left) = &node.left {
+            node = left;
+        }
+        Some(node)
+    }
+    /// Get the entry with the maximum key, if any
+    pub fn max(&self) -> Option<&Node<K, V>> {
+        let mut node = self.head.as_ref()?;
+        while let Some(right) = &node.right {
+            node = right;
+        }
+        Some(node)
+    }
+    /// Get an in-order iterator over all entries
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter {
+            front: Vec::new(),
+            back: Vec::new(),
+            remaining: self.len(),
+        };
+        iter.push_left(&self.head);
+        iter.push_right(&self.head);
+        iter
+    }
+    /// Get an in-order iterator over all keys
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+    /// Get an in-order iterator over all values
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+}
+
+///
+/// # Persistent Map Iterator
+///
+/// Lazy, double-ended, in-order iteration, via a stack of pending nodes from each end.
+/// The two ends stop upon meeting, as tracked by the count of `remaining` entries.
+///
+#[derive(Debug)]
+pub struct Iter<'t, K, V> {
+    front: Vec<&'t Node<K, V>>,
+    back: Vec<&'t Node<K, V>>,
+    remaining: usize,
+}
+impl<'t, K, V> Iter<'t, K, V> {
+    /// Push `link` and its chain of left children onto the front stack
+    fn push_left(&mut self, mut link: &'t Link<K, V>) {
+        while let Some(node) = link {
+            self.front.push(node);
+            link = &node.left;
+        }
+    }
+    /// Push `link` and its chain of right children onto the back stack
+    fn push_right(&mut self, mut link: &'t Link<K, V>) {
+        while let Some(node) = link {
+            self.back.push(node);
+            link = &node.right;
+        }
+    }
+}
+impl<'t, K, V> Iterator for Iter<'t, K, V> {
+    type Item = (&'t K, &'t V);
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.front.pop()?;
+        self.push_left(&node.right);
+        self.remaining -= 1;
+        Some((&node.key, &node.value))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+impl<'t, K, V> DoubleEndedIterator for Iter<'t, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.back.pop()?;
+        self.push_right(&node.left);
+        self.remaining -= 1;
+        Some((&node.key, &node.value))
+    }
+}
+impl<'t, K, V> ExactSizeIterator for Iter<'t, K, V> {}
+impl<'t, K, V> IntoIterator for &'t PersistentMap<K, V> {
+    type Item = (&'t K, &'t V);
+    type IntoIter = Iter<'t, K, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[test]
+fn test_persistent_versions() {
+    // Keep a history of every version, as in a configuration-set log
+    let mut history = vec![PersistentMap::new()];
+    for k in 0..50 {
+        let next = history.last().unwrap().insert(k, k * 10);
+        history.push(next);
+    }
+    for k in (0..50).step_by(3) {
+        let next = history.last().unwrap().remove(&k);
+        history.push(next);
+    }
+    // Each version retains exactly its own contents
+    for (version, map) in history.iter().enumerate().take(51) {
+        assert_eq!(map.len(), version);
+        assert!(map.check_invariants());
+        assert_eq!(
+            map.keys().copied().collect::<Vec<_>>(),
+            (0..version as i32).collect::<Vec<_>>()
+        );
+    }
+    let last = history.last().unwrap();
+    assert!(last.check_invariants());
+    assert_eq!(last.len(), 50 - 17);
+    assert_eq!(last.get(&4), Some(&40));
+    assert_eq!(last.get(&3), None);
+    assert_eq!(history[50].get(&3), Some(&30));
+
+    // Replacing a value leaves the prior version unchanged
+    let updated = last.insert(4, -1);
+    assert_eq!(updated.get(&4), Some(&-1));
+    assert_eq!(last.get(&4), Some(&40));
+}
+
+#[test]
+fn test_persistent_sharing() {
+    let mut map = PersistentMap::new();
+    for k in 0..1024 {
+        map = map.insert(k, ());
+    }
+    let snapshot = map.snapshot();
+    assert!(snapshot.ptr_eq(&map));
+
+    // Removing an absent key shares the entire tree
+    assert!(map.remove(&5000).ptr_eq(&map));
+
+    // An update copies only its search path. All other nodes are shared with the prior version.
+    fn nodes<K, V>(link: &Link<K, V>, out: &mut Vec<*const Node<K, V>>) {
+        if let Some(n) = link {
+            out.push(Rc::as_ptr(n));
+            nodes(&n.left, out);
+            nodes(&n.right, out);
+        }
+    }
+    let updated = map.insert(512, ());
+    let (mut old, mut new) = (Vec::new(), Vec::new());
+    nodes(&map.head, &mut old);
+    nodes(&updated.head, &mut new);
+    let copied = new.iter().filter(|p| !old.contains(p)).count();
+    assert!(copied <= map.head.as_ref().unwrap().height);
+    assert!(!updated.ptr_eq(&map));
+}
+
+#[test]
+fn test_persistent_queries() {
+    use std::collections::BTreeMap;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(43);
+    let mut map = PersistentMap::new();
+    let mut reference = BTreeMap::new();
+    for _ in 0..500 {
+        let k: u16 = rng.gen_range(0..300);
+        if rng.gen_bool(0.3) {
+            map = map.remove(&k);
+            reference.remove(&k);
+        } else {
+            map = map.insert(k, k as u32);
+            reference.insert(k, k as u32);
+        }
+    }
+    assert!(map.check_invariants());
+    assert_eq!(map.len(), reference.len());
+    assert!(map.iter().eq(reference.iter()));
+    assert!(map.iter().rev().eq(reference.iter().rev()));
+    assert_eq!(map.min().map(|n| n.key), reference.keys().next().copied());
+    assert_eq!(
+        map.max().map(|n| n.key),
+        reference.keys().next_back().copied()
+    );
+    assert!(map.range(50..150).eq(reference.range(50..150)));
+    assert!(map.range(..=75).rev().eq(reference.range(..=75).rev()));
+    assert_eq!(map.count_range(100..200), reference.range(100..200).count());
+
+    let keys: Vec<u16> = reference.keys().copied().collect();
+    for (k, key) in keys.iter().enumerate() {
+        assert_eq!(map.rank(key), k);
+        assert_eq!(map.select(k).map(|n| n.key), Some(*key));
+    }
+    assert!(map.select(keys.len()).is_none());
+
+    // Interleaved front and back iteration meet in the middle
+    let mut iter = map.range(10..20);
+    let mut seen = Vec::new();
+    while let Some((k, _)) = iter.next() {
+        seen.push(*k);
+        if let Some((k, _)) = iter.next_back() {
+            seen.push(*k);
+        }
+    }
+    assert_eq!(seen.len(), reference.range(10..20).count());
+}
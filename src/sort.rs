@@ -3,12 +3,177 @@
 //!
 //! Survey of popular methods, heavily inspired by @jonhoo's "Crust of Rust" treatment.
 //!
+//! Each algorithm is a unit-struct implementing the [Sorter] trait.
+//! All comparisons, swaps, and allocations are routed through an [Ops] context,
+//! so that they can optionally be tallied into [SortStats], for empirical comparison between algorithms.
+//!
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+// Local Imports
+use crate::heap;
+
+///
+/// # Sorter
+///
+/// Common interface to each sorting algorithm.
+/// Implementors provide `sort_with`, which routes all operations through an [Ops] context.
+/// The remaining methods are provided in terms of it.
+///
+pub trait Sorter<T> {
+    /// Core implementation. Sort `items` using the comparator in `ops`,
+    /// making all comparisons, swaps, and allocations through it.
+    fn sort_with<F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument;
+
+    /// Sort `items` in ascending order
+    fn sort(items: &mut [T])
+    where
+        T: Ord,
+    {
+        Self::sort_by(items, T::cmp)
+    }
+    /// Sort `items` with comparator-function `cmp`
+    fn sort_by<F>(items: &mut [T], cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        Self::sort_with(items, &mut Ops::new(cmp, &mut NoStats))
+    }
+    /// Sort `items` by the keys extracted by function `key`
+    fn sort_by_key<K, F>(items: &mut [T], mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        Self::sort_by(items, |a, b| key(a).cmp(&key(b)))
+    }
+    /// Sort `items` in ascending order, returning counts of the operations performed
+    fn sort_counted(items: &mut [T]) -> SortStats
+    where
+        T: Ord,
+    {
+        Self::sort_by_counted(items, T::cmp)
+    }
+    /// Sort `items` with comparator-function `cmp`, returning counts of the operations performed
+    fn sort_by_counted<F>(items: &mut [T], cmp: F) -> SortStats
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut stats = SortStats::default();
+        Self::sort_with(items, &mut Ops::new(cmp, &mut stats));
+        stats
+    }
+}
 
-/// Sample using the standard library's [slice::sort]
+///
+/// # Instrumentation
+///
+/// Receives a notification of each operation made through an [Ops] context.
+/// All methods default to doing nothing.
+///
+pub trait Instrument {
+    /// Notification of a single comparison
+    fn compared(&mut self) {}
+    /// Notification of `n` element-swaps
+    fn swapped(&mut self, _n: usize) {}
+    /// Notification of a single allocation
+    fn allocated(&mut self) {}
+}
+
+/// Null [Instrument], which counts nothing. Used by all un-instrumented sorts.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoStats;
+impl Instrument for NoStats {}
+
+/// Counts of the operations made during a sort
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SortStats {
+    pub comparisons: usize,
+    pub swaps: usize,
+    pub allocations: usize,
+}
+impl Instrument for SortStats {
+    fn compared(&mut self) {
+        self.comparisons += 1;
+    }
+    fn swapped(&mut self, n: usize) {
+        self.swaps += n;
+    }
+    fn allocated(&mut self) {
+        self.allocations += 1;
+    }
+}
+
+///
+/// # Sorting Operations Context
+///
+/// Combination of a comparator-function `cmp` and an [Instrument] `inst`.
+/// [Sorter] implementations make all comparisons, swaps, and allocations through its methods.
+///
+pub struct Ops<'i, F, I> {
+    cmp: F,
+    inst: &'i mut I,
+}
+impl<'i, F, I: Instrument> Ops<'i, F, I> {
+    /// Create a new [Ops] context
+    pub fn new(cmp: F, inst: &'i mut I) -> Self {
+        Self { cmp, inst }
+    }
+    /// Compare `a` and `b`
+    pub fn cmp<T>(&mut self, a: &T, b: &T) -> Ordering
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.inst.compared();
+        (self.cmp)(a, b)
+    }
+    /// Boolean indication of whether `a` sorts strictly before `b`
+    pub fn lt<T>(&mut self, a: &T, b: &T) -> bool
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.cmp(a, b) == Ordering::Less
+    }
+    /// Record `n` swaps, made outside of [Ops::swap], e.g. by [crate::heap]
+    pub(crate) fn swapped(&mut self, n: usize) {
+        self.inst.swapped(n)
+    }
+    /// Swap the elements at indices `a` and `b`
+    pub fn swap<T>(&mut self, items: &mut [T], a: usize, b: usize) {
+        self.inst.swapped(1);
+        items.swap(a, b)
+    }
+    /// Rotate `items` right by one, moving its last element to the front.
+    /// Counted as the equivalent number of adjacent swaps.
+    pub fn rotate_right_one<T>(&mut self, items: &mut [T]) {
+        self.inst.swapped(items.len().saturating_sub(1));
+        items.rotate_right(1)
+    }
+    /// Reverse `items` in place
+    pub fn reverse<T>(&mut self, items: &mut [T]) {
+        self.inst.swapped(items.len() / 2);
+        items.reverse()
+    }
+    /// Record an allocation
+    pub fn alloc(&mut self) {
+        self.inst.allocated()
+    }
+}
+
+/// Sample using the standard library's [slice::sort_by].
+/// Instrumentation counts comparisons only.
 pub struct StdLibSort;
-impl StdLibSort {
-    pub fn sort<T: Ord>(items: &mut [T]) {
-        items.sort()
+impl<T> Sorter<T> for StdLibSort {
+    fn sort_with<F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        items.sort_by(|a, b| ops.cmp(a, b))
     }
 }
 
@@ -23,14 +188,18 @@ fn test_std() {
 /// # Bubble Sort
 ///
 pub struct BubbleSort;
-impl BubbleSort {
-    pub fn sort<T: Ord>(items: &mut [T]) {
+impl<T> Sorter<T> for BubbleSort {
+    fn sort_with<F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
         let mut swapped = true;
         while swapped {
             swapped = false;
             for idx in 1..items.len() {
-                if items[idx - 1] > items[idx] {
-                    items.swap(idx - 1, idx);
+                if ops.lt(&items[idx], &items[idx - 1]) {
+                    ops.swap(items, idx - 1, idx);
                     swapped = true;
                 }
             }
@@ -49,20 +218,25 @@ fn test_bubble() {
 /// # Selection Sort
 ///
 pub struct SelectionSort;
-impl SelectionSort {
-    pub fn sort<T: Ord>(items: &mut [T]) {
+impl<T> Sorter<T> for SelectionSort {
+    fn sort_with<F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
         // For each index, swap it with the minimum
-        for idx in 0..(items.len() - 1) {
+        for idx in 0..items.len() {
             // Get the min over the remaining elements
-            let (m, _) = items[idx..]
-                .iter()
-                .enumerate()
-                .min_by_key(|&(_, v)| v)
-                .unwrap();
-            // Now, that index is into `items[idx..]`, which starts at `idx`
-            let m = m + idx;
+            let mut m = idx;
+            for k in idx + 1..items.len() {
+                if ops.lt(&items[k], &items[m]) {
+                    m = k;
+                }
+            }
             // And swap the element at `idx` with the min
-            items.swap(idx, m);
+            if m != idx {
+                ops.swap(items, idx, m);
+            }
         }
     }
 }
@@ -78,12 +252,16 @@ fn test_selection() {
 /// # Insertion Sort
 ///
 pub struct InsertionSort;
-impl InsertionSort {
-    pub fn sort<T: Ord>(items: &mut [T]) {
+impl<T> Sorter<T> for InsertionSort {
+    fn sort_with<F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
         for partition in 1..items.len() {
             let mut i = partition;
-            while i > 0 && items[i - 1] > items[i] {
-                items.swap(i - 1, i);
+            while i > 0 && ops.lt(&items[i], &items[i - 1]) {
+                ops.swap(items, i - 1, i);
                 i -= 1;
             }
         }
@@ -101,16 +279,19 @@ fn test_insertion() {
 /// # Insertion Sort, with Binary Search
 ///
 pub struct InsertionSortBinary;
-impl InsertionSortBinary {
-    pub fn sort<T: Ord>(items: &mut [T]) {
+impl<T> Sorter<T> for InsertionSortBinary {
+    fn sort_with<F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
         for partition in 1..items.len() {
-            // Find the index in the sorted-half where the partition-element belongs
-            let idx = match &items[..partition].binary_search(&items[partition]) {
-                // `binary_search` returns `Ok` on an exact hit, `Err` if not, but the correct index either way.
-                Ok(idx) | Err(idx) => *idx,
-            };
+            // Find the index in the sorted-half where the partition-element belongs.
+            // Searching for the first element *greater* than it keeps equal elements in order.
+            let (sorted, rest) = items.split_at(partition);
+            let idx = sorted.partition_point(|x| ops.cmp(x, &rest[0]) != Ordering::Greater);
             // And rotate it into place
-            items[idx..partition + 1].rotate_right(idx);
+            ops.rotate_right_one(&mut items[idx..partition + 1]);
         }
     }
 }
@@ -118,66 +299,215 @@ impl InsertionSortBinary {
 #[test]
 fn test_insertion_binary() {
     let mut items = vec![10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5];
-    InsertionSort::sort(&mut items);
+    InsertionSortBinary::sort(&mut items);
     assert_eq!(items, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
 }
 
 ///
 /// # Quick Sort
 ///
+/// Sorts with the default [QuickSortConfig]: median-of-three pivots and two-way (Hoare) partitioning.
+/// See [QuickSortConfig] for other pivot and partitioning strategies.
+///
+/// Recursion always descends into the smaller partition, and loops over the larger,
+/// bounding stack depth to O(log n) regardless of input.
+///
+/// Not stable: equal elements may be re-ordered.
+///
 pub struct QuickSort;
-impl QuickSort {
-    pub fn sort<T: Ord>(items: &mut [T]) {
-        // Call the recursive helper
-        QuickSort::helper(items)
+impl<T> Sorter<T> for QuickSort {
+    fn sort_with<F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        QuickSortConfig::default().sort_with(items, ops)
     }
 }
-impl QuickSort {
-    /// Recursive quicksort implementation
-    fn helper<T: Ord>(items: &mut [T]) {
-        match items.len() {
-            0 | 1 => return,
-            2 => {
-                if items[0] > items[1] {
-                    items.swap(0, 1);
-                }
-                return;
-            }
-            _ => (),
-        };
-        // Split out the first element as our pivot
-        let (pivot, rest) = items.split_first_mut().unwrap();
-
-        // Create the left & right index-trackers
-        let mut left = 0;
-        let mut right = rest.len() - 1;
-
-        // Make all of our pivot-swaps
-        while left <= right {
-            if rest[left] <= *pivot {
-                left += 1;
-            } else if rest[right] > *pivot {
-                if right == 0 {
-                    break; // Done, all went to the right
+
+/// Quicksort Pivot-Selection Strategies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pivot {
+    /// The first element. Degrades to O(n^2) time on sorted input.
+    First,
+    /// Median of the first, middle, and last elements
+    MedianOfThree,
+    /// Tukey's "ninther": the median of three medians-of-three, spread across the slice.
+    /// Falls back to median-of-three on short slices.
+    Ninther,
+    /// Uniformly random, via [rand]
+    Random,
+}
+
+/// Quicksort Partitioning Strategies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Partition {
+    /// Hoare-style partitioning into elements no greater than, and no less than, the pivot.
+    /// Both scans stop on elements equal to the pivot, splitting runs of duplicates evenly.
+    TwoWay,
+    /// Dijkstra's "Dutch national flag" partitioning into less-than, equal-to, and greater-than the pivot.
+    /// Elements equal to the pivot are excluded from further recursion,
+    /// making for linear time on inputs with few unique values.
+    ThreeWay,
+}
+
+/// Quicksort Configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuickSortConfig {
+    pub pivot: Pivot,
+    pub partition: Partition,
+}
+impl Default for QuickSortConfig {
+    fn default() -> Self {
+        Self {
+            pivot: Pivot::MedianOfThree,
+            partition: Partition::TwoWay,
+        }
+    }
+}
+impl QuickSortConfig {
+    /// Length at and below which slices are insertion-sorted
+    const INSERTION_THRESHOLD: usize = 16;
+    /// Length at and above which [Pivot::Ninther] samples nine elements, rather than three
+    const NINTHER_THRESHOLD: usize = 40;
+
+    /// Create a new [QuickSortConfig]
+    pub fn new(pivot: Pivot, partition: Partition) -> Self {
+        Self { pivot, partition }
+    }
+    /// Sort `items` in ascending order
+    pub fn sort<T: Ord>(&self, items: &mut [T]) {
+        self.sort_by(items, T::cmp)
+    }
+    /// Sort `items` with comparator-function `cmp`
+    pub fn sort_by<T, F>(&self, items: &mut [T], cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.sort_with(items, &mut Ops::new(cmp, &mut NoStats))
+    }
+    /// Sort `items` in ascending order, returning counts of the operations performed
+    pub fn sort_counted<T: Ord>(&self, items: &mut [T]) -> SortStats {
+        let mut stats = SortStats::default();
+        self.sort_with(items, &mut Ops::new(T::cmp, &mut stats));
+        stats
+    }
+    /// Core implementation, routing all operations through `ops`
+    pub fn sort_with<T, F, I>(&self, mut items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        while items.len() > Self::INSERTION_THRESHOLD {
+            // Move our selected pivot to the front
+            let p = self.select_pivot(items, ops);
+            ops.swap(items, 0, p);
+
+            // Partition, into the range of elements strictly left and strictly right of their final positions
+            let (left_end, right_start) = match self.partition {
+                Partition::TwoWay => {
+                    let p = partition_two_way(items, ops);
+                    (p, p + 1)
                 }
-                right -= 1;
+                Partition::ThreeWay => partition_three_way(items, ops),
+            };
+
+            // Recursively sort the smaller side, and continue looping over the larger
+            let (left, rest) = std::mem::take(&mut items).split_at_mut(left_end);
+            let right = &mut rest[right_start - left_end..];
+            if left.len() < right.len() {
+                self.sort_with(left, ops);
+                items = right;
             } else {
-                rest.swap(left, right);
-                left += 1;
-                if right == 0 {
-                    break; // Done, all went to the right
-                }
-                right -= 1;
+                self.sort_with(right, ops);
+                items = left;
+            }
+        }
+        InsertionSort::sort_with(items, ops)
+    }
+    /// Select the index of a pivot from `items`, which must be non-empty
+    fn select_pivot<T, F, I>(&self, items: &[T], ops: &mut Ops<'_, F, I>) -> usize
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        let len = items.len();
+        match self.pivot {
+            Pivot::First => 0,
+            Pivot::MedianOfThree => median_of_three(items, 0, len / 2, len - 1, ops),
+            Pivot::Ninther if len < Self::NINTHER_THRESHOLD => {
+                median_of_three(items, 0, len / 2, len - 1, ops)
+            }
+            Pivot::Ninther => {
+                let step = len / 8;
+                let a = median_of_three(items, 0, step, 2 * step, ops);
+                let b = median_of_three(items, len / 2 - step, len / 2, len / 2 + step, ops);
+                let c = median_of_three(items, len - 1 - 2 * step, len - 1 - step, len - 1, ops);
+                median_of_three(items, a, b, c, ops)
+            }
+            Pivot::Random => {
+                use rand::Rng;
+                rand::thread_rng().gen_range(0..len)
             }
         }
-        // Swap the pivot to its correct location
-        items.swap(0, left);
+    }
+}
 
-        // Split into two sub-slices, and recursively quicksort them
-        let (left, right) = items.split_at_mut(left);
-        Self::helper(left);
-        Self::helper(&mut right[1..]);
+/// Hoare-style partition of `items` around the pivot at `items[0]`.
+/// Returns the pivot's final index, with all elements left of it no greater, and all to its right no less.
+fn partition_two_way<T, F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+    I: Instrument,
+{
+    let (mut i, mut j) = (0, items.len());
+    loop {
+        // Scan right for an element no less than the pivot
+        i += 1;
+        while i < items.len() && ops.lt(&items[i], &items[0]) {
+            i += 1;
+        }
+        // Scan left for an element no greater than the pivot. The pivot itself stops this scan.
+        j -= 1;
+        while ops.lt(&items[0], &items[j]) {
+            j -= 1;
+        }
+        if i >= j {
+            break;
+        }
+        ops.swap(items, i, j);
     }
+    // Swap the pivot into its final place
+    ops.swap(items, 0, j);
+    j
+}
+
+/// Three-way ("Dutch national flag") partition of `items` around the pivot at `items[0]`.
+/// Returns the range `(lt, gt)` of elements equal to the pivot, with smaller elements before and larger after.
+///
+/// Rather than holding a copy of the pivot, compares against `items[lt]`,
+/// the first of the (always non-empty) run of elements equal to it.
+fn partition_three_way<T, F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>) -> (usize, usize)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    I: Instrument,
+{
+    let (mut lt, mut i, mut gt) = (0, 1, items.len());
+    while i < gt {
+        match ops.cmp(&items[i], &items[lt]) {
+            Ordering::Less => {
+                ops.swap(items, lt, i);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                ops.swap(items, i, gt);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+    (lt, gt)
 }
 
 #[test]
@@ -187,23 +517,320 @@ fn test_quick() {
     assert_eq!(items, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
 }
 
+#[test]
+fn test_quick_configs() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let pivots = [
+        Pivot::First,
+        Pivot::MedianOfThree,
+        Pivot::Ninther,
+        Pivot::Random,
+    ];
+    let partitions = [Partition::TwoWay, Partition::ThreeWay];
+
+    let mut rng = StdRng::seed_from_u64(31);
+    let inputs: Vec<Vec<i32>> = vec![
+        vec![],
+        vec![1],
+        (0..1_000).collect(),
+        (0..1_000).rev().collect(),
+        vec![4; 1_000],
+        (0..1_000).map(|_| rng.gen_range(0..5)).collect(),
+        (0..1_000).map(|_| rng.gen()).collect(),
+    ];
+    for &pivot in pivots.iter() {
+        for &partition in partitions.iter() {
+            let config = QuickSortConfig::new(pivot, partition);
+            for input in inputs.iter() {
+                let mut correct = input.clone();
+                StdLibSort::sort(&mut correct);
+                let mut items = input.clone();
+                config.sort(&mut items);
+                assert_eq!(items, correct);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_quick_sorted_and_equal() {
+    // Large sorted and all-equal inputs, each of which formerly degraded to quadratic time and linear stack depth
+    let mut items: Vec<i32> = (0..200_000).collect();
+    QuickSort::sort(&mut items);
+    assert!(items.windows(2).all(|w| w[0] <= w[1]));
+
+    let mut items = vec![7; 200_000];
+    let stats = QuickSort::sort_counted(&mut items);
+    assert!(stats.comparisons < 10_000_000);
+
+    // Three-way partitioning handles all-equal inputs in a single, linear pass
+    let mut items = vec![7; 200_000];
+    let stats = QuickSortConfig::new(Pivot::Ninther, Partition::ThreeWay).sort_counted(&mut items);
+    assert!(stats.comparisons < 400_000);
+}
+
+///
+/// # Quick Select
+///
+/// Selection and partial-sorting, built on [QuickSort]'s three-way partitioning.
+///
+/// Selection runs "introselect": quickselect with median-of-three pivots,
+/// which switches to median-of-medians pivots if partitions repeatedly fail to shrink,
+/// guaranteeing O(n) worst-case time.
+///
+pub struct QuickSelect;
+impl QuickSelect {
+    /// Length at and below which slices are insertion-sorted
+    const INSERTION_THRESHOLD: usize = 16;
+
+    /// Re-order `items` such that `items[k]` is the element which would be there if sorted,
+    /// all elements before it are no greater, and all after it are no less.
+    /// Returns a reference to `items[k]`. Panics if `k` is out of bounds.
+    pub fn select_nth<T: Ord>(items: &mut [T], k: usize) -> &mut T {
+        Self::select_nth_by(items, k, T::cmp)
+    }
+    /// Select the `k`th element of `items`, as in `select_nth`, with comparator-function `cmp`
+    pub fn select_nth_by<T, F>(items: &mut [T], k: usize, cmp: F) -> &mut T
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        assert!(k < items.len(), "select_nth index out of bounds");
+        Self::select_with(items, k, &mut Ops::new(cmp, &mut NoStats));
+        &mut items[k]
+    }
+    /// Get the element at quantile `q`, between zero (the min) and one (the max),
+    /// via `select_nth` on the nearest index. Panics if `items` is empty or `q` is out of range.
+    pub fn quantile<T: Ord>(items: &mut [T], q: f64) -> &mut T {
+        assert!((0.0..=1.0).contains(&q), "quantile out of range");
+        let k = ((items.len() - 1) as f64 * q).round() as usize;
+        Self::select_nth(items, k)
+    }
+    /// Re-order `items` such that its first `k` elements are its smallest, in sorted order.
+    /// The order of the remainder is unspecified.
+    pub fn partial_sort<T: Ord>(items: &mut [T], k: usize) {
+        Self::partial_sort_by(items, k, T::cmp)
+    }
+    /// Partially sort `items`, as in `partial_sort`, with comparator-function `cmp`
+    pub fn partial_sort_by<T, F>(items: &mut [T], k: usize, cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut stats = NoStats;
+        let ops = &mut Ops::new(cmp, &mut stats);
+        let k = k.min(items.len());
+        if k == 0 {
+            return;
+        }
+        // Select the `k`th-smallest, partitioning the smallest `k` to its left. Then sort them.
+        Self::select_with(items, k - 1, ops);
+        QuickSort::sort_with(&mut items[..k - 1], ops);
+    }
+    /// Move the `k` largest elements of `items` to its front, in descending order.
+    /// Returns that sub-slice.
+    pub fn top_k<T: Ord>(items: &mut [T], k: usize) -> &mut [T] {
+        let k = k.min(items.len());
+        Self::partial_sort_by(items, k, |a, b| b.cmp(a));
+        &mut items[..k]
+    }
+    /// Core implementation of selection, routing all operations through `ops`
+    pub fn select_with<T, F, I>(mut items: &mut [T], mut k: usize, ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        // Allow a logarithmic number of poor partitions, before switching to median-of-medians
+        let mut allowance = (usize::BITS - items.len().leading_zeros()) as usize;
+        loop {
+            if items.len() <= Self::INSERTION_THRESHOLD {
+                return InsertionSort::sort_with(items, ops);
+            }
+            let len = items.len();
+            let p = if allowance > 0 {
+                median_of_three(items, 0, len / 2, len - 1, ops)
+            } else {
+                Self::median_of_medians(items, ops)
+            };
+            ops.swap(items, 0, p);
+            let (lt, gt) = partition_three_way(items, ops);
+
+            // Narrow down to the partition containing `k`, or return if it equals the pivot
+            items = if k < lt {
+                &mut std::mem::take(&mut items)[..lt]
+            } else if k >= gt {
+                k -= gt;
+                &mut std::mem::take(&mut items)[gt..]
+            } else {
+                return;
+            };
+            if items.len() > len * 3 / 4 {
+                allowance = allowance.saturating_sub(1);
+            }
+        }
+    }
+    /// Find an approximate median of `items`, guaranteed to fall between its 30th and 70th percentiles.
+    /// Moves the median of each group of five to the front of `items`, then recursively selects their median.
+    /// Returns its index.
+    fn median_of_medians<T, F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>) -> usize
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        let mut ngroups = 0;
+        for start in (0..items.len()).step_by(5) {
+            let end = (start + 5).min(items.len());
+            InsertionSort::sort_with(&mut items[start..end], ops);
+            ops.swap(items, ngroups, (start + end) / 2);
+            ngroups += 1;
+        }
+        let mid = ngroups / 2;
+        Self::select_with(&mut items[..ngroups], mid, ops);
+        mid
+    }
+}
+
+#[test]
+fn test_select_nth() {
+    let mut items = vec![10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5];
+    assert_eq!(*QuickSelect::select_nth(&mut items, 0), 0);
+    assert_eq!(*QuickSelect::select_nth(&mut items, 10), 10);
+    assert_eq!(*QuickSelect::select_nth(&mut items, 3), 3);
+    assert!(items[..3].iter().all(|&i| i < 3));
+    assert!(items[4..].iter().all(|&i| i > 3));
+
+    let mut items: Vec<i32> = (0..1_001).rev().collect();
+    assert_eq!(*QuickSelect::quantile(&mut items, 0.5), 500);
+    assert_eq!(*QuickSelect::quantile(&mut items, 0.9), 900);
+    assert_eq!(*QuickSelect::quantile(&mut items, 1.0), 1_000);
+}
+
+#[test]
+fn test_select_nth_random() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(32);
+    for &len in [1, 5, 17, 100, 2_000].iter() {
+        let items: Vec<i32> = (0..len).map(|_| rng.gen_range(0..len / 3 + 1)).collect();
+        let mut correct = items.clone();
+        StdLibSort::sort(&mut correct);
+        for _ in 0..10 {
+            let k = rng.gen_range(0..len) as usize;
+            let mut items = items.clone();
+            assert_eq!(*QuickSelect::select_nth(&mut items, k), correct[k]);
+            assert!(items[..k].iter().all(|i| *i <= correct[k]));
+            assert!(items[k..].iter().all(|i| *i >= correct[k]));
+        }
+    }
+}
+
+#[test]
+fn test_select_median_of_medians() {
+    // Force the median-of-medians pivots from the outset, and check for linear comparison counts
+    let len = 100_000;
+    for input in [
+        (0..len).collect::<Vec<i32>>(),
+        (0..len).rev().collect(),
+        vec![3; len as usize],
+    ] {
+        let mut items = input.clone();
+        let mut stats = SortStats::default();
+        let mut ops = Ops::new(i32::cmp, &mut stats);
+        let mid = QuickSelect::median_of_medians(&mut items, &mut ops);
+        let pivot = items[mid];
+        let rank = input.iter().filter(|&&i| i < pivot).count();
+        assert!(rank <= 7 * len as usize / 10);
+        assert!(input.iter().filter(|&&i| i > pivot).count() <= 7 * len as usize / 10);
+
+        let mut items = input.clone();
+        let mut stats = SortStats::default();
+        QuickSelect::select_with(
+            &mut items,
+            len as usize / 2,
+            &mut Ops::new(i32::cmp, &mut stats),
+        );
+        assert!(stats.comparisons < 20 * len as usize);
+    }
+}
+
+#[test]
+fn test_partial_sort() {
+    let mut items = vec![10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5];
+    QuickSelect::partial_sort(&mut items, 4);
+    assert_eq!(&items[..4], &[0, 1, 2, 3]);
+
+    let mut items: Vec<i32> = (0..100).map(|i| (i * 37) % 100).collect();
+    assert_eq!(QuickSelect::top_k(&mut items, 3), &[99, 98, 97]);
+    assert_eq!(QuickSelect::top_k(&mut items, 0), &[] as &[i32]);
+    assert_eq!(QuickSelect::top_k(&mut items, 1_000).len(), 100);
+}
+
 ///
 /// # Merge Sort
 ///
+/// Sorts a permutation of indices into `items`, rather than the items themselves,
+/// so that elements are never cloned, and need be neither [Clone] nor [Default].
+/// Indices are merge-sorted back and forth between the two halves of a [MergeBuffer],
+/// after which `items` are moved into place by following each cycle of the permutation,
+/// making at most `n - 1` swaps.
+///
+/// Callers sorting repeatedly can supply and reuse a [MergeBuffer] via [MergeSort::sort_with_buffer],
+/// which only allocates if the buffer must grow.
+///
+/// Stable: equal elements retain their relative order.
+///
 pub struct MergeSort;
-impl MergeSort {
+impl<T> Sorter<T> for MergeSort {
     /// Primary implementation.
-    /// Creates a copy of `items` and begins recursive calls.
-    pub fn sort<T: Ord + Clone + Default>(items: &mut [T]) {
-        let mut cp = Self::copy(items);
-        Self::mergesort(items, &mut cp)
+    /// Sorts with a new, single-use [MergeBuffer].
+    fn sort_with<F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        Self::sort_with_buffer(items, &mut MergeBuffer::new(), ops)
+    }
+}
+impl MergeSort {
+    /// Sort `items` in ascending order, using scratch-space `buf`
+    pub fn sort_buffered<T: Ord>(items: &mut [T], buf: &mut MergeBuffer) {
+        Self::sort_by_buffered(items, buf, T::cmp)
+    }
+    /// Sort `items` with comparator-function `cmp`, using scratch-space `buf`
+    pub fn sort_by_buffered<T, F>(items: &mut [T], buf: &mut MergeBuffer, cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        Self::sort_with_buffer(items, buf, &mut Ops::new(cmp, &mut NoStats))
     }
-    /// Recursive merge-sort helper routine
-    fn mergesort<T: Ord + Clone + Default>(a: &mut [T], b: &mut [T]) {
+    /// Sort `items` using the comparator in `ops`, and scratch-space `buf`.
+    /// Notifies `ops` of an allocation only if `buf` must grow.
+    pub fn sort_with_buffer<T, F, I>(
+        items: &mut [T],
+        buf: &mut MergeBuffer,
+        ops: &mut Ops<'_, F, I>,
+    ) where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        if buf.reset(items.len()) {
+            ops.alloc();
+        }
+        let MergeBuffer { order, scratch } = buf;
+        Self::mergesort(items, order, scratch, ops);
+        Self::permute(items, order, scratch, ops);
+    }
+    /// Recursive merge-sort helper routine.
+    /// Sorts the indices in `a` by the `items` they refer to,
+    /// using `b` (which must initially equal `a`) as scratch-space.
+    fn mergesort<T, F, I>(items: &[T], a: &mut [usize], b: &mut [usize], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
         match a.len() {
             0 | 1 => return,
             2 => {
-                if a[0] > a[1] {
+                if ops.lt(&items[a[1]], &items[a[0]]) {
                     a.swap(0, 1);
                 }
                 return;
@@ -216,31 +843,96 @@ impl MergeSort {
         let b_halves = b.split_at_mut(b.len() / 2);
 
         // Merge-sort each half, swapping roles on each recursive call
-        Self::mergesort(b_halves.0, a_halves.0); // Left
-        Self::mergesort(b_halves.1, a_halves.1); // Right
-        drop(a_halves);
+        Self::mergesort(items, b_halves.0, a_halves.0, ops); // Left
+        Self::mergesort(items, b_halves.1, a_halves.1, ops); // Right
 
-        // Merge the two halves of `a` into `b`
-        Self::merge(b_halves.0, b_halves.1, a);
+        // Merge the two halves of `b` into `a`
+        Self::merge(b_halves.0, b_halves.1, a, |i, j| {
+            ops.lt(&items[i], &items[j])
+        });
     }
-    /// Merge `left` and `right` into `dest`.
+    /// Merge index-lists `left` and `right` into `dest`, ordered by less-than function `lt`.
     /// Requires that len(left) + len(right) == len(dest), or panics.
-    fn merge<T: Ord + Clone + Default>(left: &[T], right: &[T], dest: &mut [T]) {
+    /// Takes from `left` on ties, retaining stability.
+    fn merge(
+        left: &[usize],
+        right: &[usize],
+        dest: &mut [usize],
+        mut lt: impl FnMut(usize, usize) -> bool,
+    ) {
         let mut lp = 0;
         let mut rp = 0;
         for entry in dest.iter_mut() {
-            if lp < left.len() && (rp >= right.len() || left[lp] < right[rp]) {
-                *entry = left[lp].clone();
+            if lp < left.len() && (rp >= right.len() || !lt(right[rp], left[lp])) {
+                *entry = left[lp];
                 lp += 1;
             } else {
-                *entry = right[rp].clone();
+                *entry = right[rp];
                 rp += 1;
             }
         }
     }
-    /// Copy `items` to a new [Vec]
-    fn copy<T: Ord + Clone + Default>(items: &mut [T]) -> Vec<T> {
-        items.to_vec()
+    /// Move `items` into the order listed in `order`, whose entries are original indices.
+    /// Uses `dest` as scratch-space, and follows each cycle of the permutation, swapping elements into place.
+    fn permute<T, F, I>(
+        items: &mut [T],
+        order: &[usize],
+        dest: &mut [usize],
+        ops: &mut Ops<'_, F, I>,
+    ) where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        // Invert the order, to get the destination of each element
+        for (pos, &idx) in order.iter().enumerate() {
+            dest[idx] = pos;
+        }
+        for i in 0..items.len() {
+            while dest[i] != i {
+                let d = dest[i];
+                ops.swap(items, i, d);
+                dest.swap(i, d);
+            }
+        }
+    }
+}
+
+///
+/// # Merge Sort Scratch Buffer
+///
+/// Reusable scratch-space for [MergeSort], holding two index-lists as long as the longest slice sorted.
+/// Also used internally by [TimSort], for each of its merges.
+///
+#[derive(Debug, Default, Clone)]
+pub struct MergeBuffer {
+    order: Vec<usize>,
+    scratch: Vec<usize>,
+}
+impl MergeBuffer {
+    /// Create a new, empty [MergeBuffer]. Also available via [Default].
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Create a new [MergeBuffer] able to sort slices of up to `len` elements without allocating
+    pub fn with_capacity(len: usize) -> Self {
+        Self {
+            order: Vec::with_capacity(len),
+            scratch: Vec::with_capacity(len),
+        }
+    }
+    /// Get the length of the longest slice sortable without allocating
+    pub fn capacity(&self) -> usize {
+        self.order.capacity().min(self.scratch.capacity())
+    }
+    /// Reset both index-lists to the identity permutation of length `len`.
+    /// Returns a boolean indication of whether this required growing the buffer.
+    fn reset(&mut self, len: usize) -> bool {
+        let grow = self.capacity() < len;
+        self.order.clear();
+        self.order.extend(0..len);
+        self.scratch.clear();
+        self.scratch.extend(0..len);
+        grow
     }
 }
 
@@ -250,3 +942,1159 @@ fn test_merge() {
     MergeSort::sort(&mut items);
     assert_eq!(items, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
 }
+
+#[test]
+fn test_merge_buffered() {
+    // Neither `Clone` nor `Default`
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Record(i32, String);
+
+    let mut buf = MergeBuffer::new();
+    let mut items: Vec<Record> = (0..50).map(|i| Record(i % 5, i.to_string())).collect();
+    MergeSort::sort_by_buffered(&mut items, &mut buf, |a, b| a.0.cmp(&b.0));
+    for pair in items.windows(2) {
+        assert!(pair[0].0 <= pair[1].0);
+        if pair[0].0 == pair[1].0 {
+            // Stable: original order retained among equals
+            let (a, b): (i32, i32) = (pair[0].1.parse().unwrap(), pair[1].1.parse().unwrap());
+            assert!(a < b);
+        }
+    }
+    assert!(buf.capacity() >= 50);
+
+    // Re-using the buffer for no-longer inputs does not allocate
+    let mut items: Vec<Record> = (0..50).rev().map(|i| Record(i, String::new())).collect();
+    let mut stats = SortStats::default();
+    MergeSort::sort_with_buffer(&mut items, &mut buf, &mut Ops::new(Record::cmp, &mut stats));
+    assert_eq!(stats.allocations, 0);
+    assert!(stats.swaps < 50);
+    assert!(items.iter().map(|r| r.0).eq(0..50));
+}
+
+///
+/// # Heap Sort
+///
+/// Arranges `items` into a max-heap, using the sift-down of [crate::heap::Heap],
+/// then repeatedly swaps the max to the end of the shrinking heap-region.
+///
+pub struct HeapSort;
+impl<T> Sorter<T> for HeapSort {
+    fn sort_with<F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        // Arrange `items` into a max-heap, in which larger items sit closer to the top
+        let swaps = heap::heapify(items, |a, b| ops.lt(b, a));
+        ops.swapped(swaps);
+        // Repeatedly swap the max to the end of the heap-region, and shrink it
+        for end in (1..items.len()).rev() {
+            ops.swap(items, 0, end);
+            let swaps = heap::siftdown(&mut items[..end], 0, |a, b| ops.lt(b, a));
+            ops.swapped(swaps);
+        }
+    }
+}
+
+#[test]
+fn test_heap_sort() {
+    let mut items = vec![10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5];
+    HeapSort::sort(&mut items);
+    assert_eq!(items, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+    let mut items: Vec<usize> = vec![];
+    HeapSort::sort(&mut items);
+    assert!(items.is_empty());
+}
+
+/// Shared test battery for each [Sorter]. Checks results against [StdLibSort].
+#[cfg(test)]
+fn check_sorter<S: Sorter<i32>>() {
+    let cases: Vec<Vec<i32>> = vec![
+        vec![],
+        vec![1],
+        vec![2, 1],
+        vec![1, 2, 3, 4, 5, 6, 7, 8],
+        vec![8, 7, 6, 5, 4, 3, 2, 1],
+        vec![3, 3, 3, 3, 3],
+        vec![5, -1, 3, 3, 0, 9, -7, 3, 2, 2, 8, 1],
+    ];
+    for case in cases {
+        let mut correct = case.clone();
+        StdLibSort::sort(&mut correct);
+
+        let mut items = case.clone();
+        S::sort(&mut items);
+        assert_eq!(items, correct);
+
+        // Descending, via `sort_by`
+        let mut items = case.clone();
+        S::sort_by(&mut items, |a, b| b.cmp(a));
+        correct.reverse();
+        assert_eq!(items, correct);
+    }
+}
+
+/// Check that [Sorter] `S` is stable, sorting `(key, index)` pairs by key
+#[cfg(test)]
+fn check_stable<S: Sorter<(i32, usize)>>() {
+    let keys = [3, 1, 2, 3, 1, 2, 3, 1, 2, 0, 0, 3];
+    let mut items: Vec<(i32, usize)> = keys.iter().copied().zip(0..).collect();
+    S::sort_by_key(&mut items, |&(k, _)| k);
+    for pair in items.windows(2) {
+        assert!(pair[0].0 < pair[1].0 || (pair[0].0 == pair[1].0 && pair[0].1 < pair[1].1));
+    }
+}
+
+#[test]
+fn test_sorters() {
+    check_sorter::<StdLibSort>();
+    check_sorter::<BubbleSort>();
+    check_sorter::<SelectionSort>();
+    check_sorter::<InsertionSort>();
+    check_sorter::<InsertionSortBinary>();
+    check_sorter::<QuickSort>();
+    check_sorter::<MergeSort>();
+    check_sorter::<HeapSort>();
+
+    check_stable::<StdLibSort>();
+    check_stable::<BubbleSort>();
+    check_stable::<InsertionSort>();
+    check_stable::<InsertionSortBinary>();
+    check_stable::<MergeSort>();
+}
+
+#[test]
+fn test_sort_stats() {
+    // Already-sorted input: a single bubble-sort pass, with no swaps
+    let mut items: Vec<i32> = (0..10).collect();
+    let stats = BubbleSort::sort_counted(&mut items);
+    assert_eq!(
+        stats,
+        SortStats {
+            comparisons: 9,
+            swaps: 0,
+            allocations: 0
+        }
+    );
+
+    // Reversed input: insertion sort swaps every pair
+    let mut items: Vec<i32> = (0..10).rev().collect();
+    let stats = InsertionSort::sort_counted(&mut items);
+    assert_eq!(stats.swaps, 45);
+    assert_eq!(stats.comparisons, 45);
+
+    // Merge sort allocates its copy
+    let mut items: Vec<i32> = (0..10).rev().collect();
+    let stats = MergeSort::sort_counted(&mut items);
+    assert_eq!(stats.allocations, 1);
+    assert!(stats.comparisons > 0);
+}
+
+///
+/// # Shell Sort
+///
+/// Insertion sort over a shrinking sequence of gaps, using Ciura's gap sequence,
+/// extended by factors of 2.25 for longer inputs.
+///
+/// Not stable: equal elements may be re-ordered.
+///
+pub struct ShellSort;
+impl<T> Sorter<T> for ShellSort {
+    fn sort_with<F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        for gap in Self::gaps(items.len()).into_iter().rev() {
+            // Gapped insertion sort
+            for partition in gap..items.len() {
+                let mut i = partition;
+                while i >= gap && ops.lt(&items[i], &items[i - gap]) {
+                    ops.swap(items, i - gap, i);
+                    i -= gap;
+                }
+            }
+        }
+    }
+}
+impl ShellSort {
+    /// Ciura's empirically-derived gap sequence
+    const CIURA: [usize; 8] = [1, 4, 10, 23, 57, 132, 301, 701];
+
+    /// Get the increasing list of gaps smaller than `len`
+    fn gaps(len: usize) -> Vec<usize> {
+        let mut gaps: Vec<usize> = Self::CIURA.iter().copied().filter(|&g| g < len).collect();
+        if gaps.len() == Self::CIURA.len() {
+            // Extend beyond Ciura's sequence, by factors of 2.25
+            let mut gap = *gaps.last().unwrap() * 9 / 4;
+            while gap < len {
+                gaps.push(gap);
+                gap = gap * 9 / 4;
+            }
+        }
+        gaps
+    }
+}
+
+#[test]
+fn test_shell() {
+    let mut items = vec![10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5];
+    ShellSort::sort(&mut items);
+    assert_eq!(items, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+}
+
+///
+/// # Intro Sort
+///
+/// [QuickSort] with median-of-three pivots, which falls back to [HeapSort]
+/// once its recursion exceeds twice the (base-two) log of the input length,
+/// guaranteeing O(n log n) time. Short sub-slices are finished with [InsertionSort].
+///
+/// Not stable: equal elements may be re-ordered.
+///
+pub struct IntroSort;
+impl<T> Sorter<T> for IntroSort {
+    fn sort_with<F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        let depth = 2 * (usize::BITS - items.len().leading_zeros()) as usize;
+        Self::helper(items, depth, ops)
+    }
+}
+impl IntroSort {
+    /// Length at and below which sub-slices are insertion-sorted
+    const INSERTION_THRESHOLD: usize = 16;
+
+    /// Recursive helper, with remaining recursion-depth `depth`
+    fn helper<T, F, I>(items: &mut [T], depth: usize, ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        if items.len() <= Self::INSERTION_THRESHOLD {
+            return InsertionSort::sort_with(items, ops);
+        }
+        if depth == 0 {
+            return HeapSort::sort_with(items, ops);
+        }
+        // Move the median of the first, middle, and last elements to the front, as our pivot
+        let m = median_of_three(items, 0, items.len() / 2, items.len() - 1, ops);
+        ops.swap(items, 0, m);
+
+        // Partition around it, shared with [QuickSort]
+        let p = partition_two_way(items, ops);
+
+        // And recursively sort each side
+        let (left, right) = items.split_at_mut(p);
+        Self::helper(left, depth - 1, ops);
+        Self::helper(&mut right[1..], depth - 1, ops);
+    }
+}
+
+/// Get the index of the median of the elements at indices `a`, `b`, and `c`
+fn median_of_three<T, F, I>(
+    items: &[T],
+    a: usize,
+    b: usize,
+    c: usize,
+    ops: &mut Ops<'_, F, I>,
+) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+    I: Instrument,
+{
+    let ab = ops.lt(&items[a], &items[b]);
+    let bc = ops.lt(&items[b], &items[c]);
+    if ab == bc {
+        return b; // `b` is between the two
+    }
+    let ac = ops.lt(&items[a], &items[c]);
+    if ab == ac {
+        c
+    } else {
+        a
+    }
+}
+
+#[test]
+fn test_intro() {
+    let mut items = vec![10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5];
+    IntroSort::sort(&mut items);
+    assert_eq!(items, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+    // All-equal input drives the partitioning into its worst case, and the heap-sort fallback
+    let mut items = vec![7; 1_000];
+    let stats = IntroSort::sort_counted(&mut items);
+    assert!(stats.comparisons < 100_000);
+}
+
+///
+/// # Tim Sort
+///
+/// Simplified version of Tim Peters' hybrid merge sort:
+/// * Scans for natural runs, either non-descending or strictly descending (which are reversed in place).
+/// * Extends short runs to a minimum length with binary insertion sort.
+/// * Merges runs from a stack, maintaining Timsort's run-length invariants.
+///
+/// Omits the original's "galloping" mode. Merges work as do those of [MergeSort]:
+/// they merge a permutation of indices in a [MergeBuffer], then move elements into place,
+/// so that elements are never cloned.
+///
+/// Stable: equal elements retain their relative order.
+///
+pub struct TimSort;
+impl<T> Sorter<T> for TimSort {
+    fn sort_with<F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        let len = items.len();
+        if len < 2 {
+            return;
+        }
+        let minrun = Self::minrun(len);
+        let mut runs: Vec<Run> = Vec::new();
+        let mut buf = MergeBuffer::new();
+
+        let mut start = 0;
+        while start < len {
+            // Find the next natural run, and extend it to `minrun` if short
+            let mut run_len = Self::count_run(&mut items[start..], ops);
+            if run_len < minrun {
+                let forced = minrun.min(len - start);
+                Self::binary_insertion(&mut items[start..start + forced], run_len, ops);
+                run_len = forced;
+            }
+            runs.push(Run {
+                start,
+                len: run_len,
+            });
+            Self::collapse(items, &mut runs, &mut buf, ops, false);
+            start += run_len;
+        }
+        // Merge everything remaining on the stack
+        Self::collapse(items, &mut runs, &mut buf, ops, true);
+    }
+}
+
+/// Run of sorted elements, used by [TimSort]
+#[derive(Debug, Clone, Copy)]
+struct Run {
+    start: usize,
+    len: usize,
+}
+
+impl TimSort {
+    /// Compute the minimum run-length for an input of length `n`.
+    /// Takes the top six bits of `n`, plus one if any of the remaining bits are set.
+    fn minrun(mut n: usize) -> usize {
+        let mut r = 0;
+        while n >= 64 {
+            r |= n & 1;
+            n >>= 1;
+        }
+        n + r
+    }
+    /// Find the length of the run at the start of `items`.
+    /// Strictly-descending runs are reversed in place. (Strictly, so that reversal retains stability.)
+    fn count_run<T, F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>) -> usize
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        if items.len() < 2 {
+            return items.len();
+        }
+        let mut end = 2;
+        if ops.lt(&items[1], &items[0]) {
+            while end < items.len() && ops.lt(&items[end], &items[end - 1]) {
+                end += 1;
+            }
+            ops.reverse(&mut items[..end]);
+        } else {
+            while end < items.len() && !ops.lt(&items[end], &items[end - 1]) {
+                end += 1;
+            }
+        }
+        end
+    }
+    /// Binary-insertion-sort `items`, the first `sorted` of which are already in order
+    fn binary_insertion<T, F, I>(items: &mut [T], sorted: usize, ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        for partition in sorted.max(1)..items.len() {
+            let (sorted, rest) = items.split_at(partition);
+            let idx = sorted.partition_point(|x| ops.cmp(x, &rest[0]) != Ordering::Greater);
+            ops.rotate_right_one(&mut items[idx..partition + 1]);
+        }
+    }
+    /// Merge runs atop the stack `runs` until its invariants hold:
+    /// each run must be longer than the sum of the two above it, and longer than the one directly above.
+    /// If `force`, merge down to a single run.
+    fn collapse<T, F, I>(
+        items: &mut [T],
+        runs: &mut Vec<Run>,
+        buf: &mut MergeBuffer,
+        ops: &mut Ops<'_, F, I>,
+        force: bool,
+    ) where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        while runs.len() > 1 {
+            let mut n = runs.len() - 2;
+            let violates_three = |n: usize, runs: &[Run]| {
+                (n > 0 && runs[n - 1].len <= runs[n].len + runs[n + 1].len)
+                    || (n > 1 && runs[n - 2].len <= runs[n - 1].len + runs[n].len)
+            };
+            if force || violates_three(n, runs) {
+                if n > 0 && runs[n - 1].len < runs[n + 1].len {
+                    n -= 1;
+                }
+            } else if runs[n].len > runs[n + 1].len {
+                break; // Invariants hold. Done.
+            }
+            // Merge runs `n` and `n+1`
+            let (a, b) = (runs[n], runs[n + 1]);
+            Self::merge(&mut items[a.start..b.start + b.len], a.len, buf, ops);
+            runs[n].len += b.len;
+            runs.remove(n + 1);
+        }
+    }
+    /// Merge the sorted sub-slices `items[..mid]` and `items[mid..]`, using scratch-space `buf`.
+    /// Notifies `ops` of an allocation whenever `buf` must grow.
+    /// Takes from the left on ties, retaining stability.
+    fn merge<T, F, I>(items: &mut [T], mid: usize, buf: &mut MergeBuffer, ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        if buf.reset(items.len()) {
+            ops.alloc();
+        }
+        // Merge the indices of the two runs, and then move each element to its merged position
+        let MergeBuffer { order, scratch } = buf;
+        let (left, right) = scratch.split_at(mid);
+        MergeSort::merge(left, right, order, |i, j| ops.lt(&items[i], &items[j]));
+        MergeSort::permute(items, order, scratch, ops);
+    }
+}
+
+#[test]
+fn test_tim() {
+    let mut items = vec![10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5];
+    TimSort::sort(&mut items);
+    assert_eq!(items, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+    // Natural runs are detected, and require no more than a linear number of comparisons
+    let mut items: Vec<i32> = (0..500).chain((0..500).rev()).collect();
+    let stats = TimSort::sort_counted(&mut items);
+    assert_eq!(items, (0..500).flat_map(|k| [k, k]).collect::<Vec<_>>());
+    assert!(stats.comparisons < 2_000);
+
+    // Neither `Clone` nor `Default`. Merges of growing length each grow the scratch buffer.
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Record(i32, String);
+    let mut items: Vec<Record> = (0..1_000)
+        .rev()
+        .map(|i| Record(i % 300, i.to_string()))
+        .collect();
+    let stats = TimSort::sort_counted(&mut items);
+    assert!(items.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert!(stats.allocations > 1);
+}
+
+///
+/// # Bucket Sort
+///
+/// Comparison-based bucket sort, sometimes called "sample sort".
+/// Picks roughly `sqrt(n)` splitters from evenly-spaced positions, and distributes each element
+/// into the bucket between its neighbouring splitters, found by binary search.
+/// Each bucket is then sorted recursively. Short slices are finished with [InsertionSort],
+/// and buckets which fail to divide their input (e.g. due to many equal elements) with [MergeSort].
+///
+/// Stable: equal elements retain their relative order.
+/// Elements are distributed in their original order, and equal elements always share a bucket.
+///
+pub struct BucketSort;
+impl<T> Sorter<T> for BucketSort {
+    fn sort_with<F, I>(items: &mut [T], ops: &mut Ops<'_, F, I>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        let len = items.len();
+        if len <= Self::INSERTION_THRESHOLD {
+            return InsertionSort::sort_with(items, ops);
+        }
+        let splitters = Self::splitters(items, ops);
+        ops.alloc();
+
+        // Find each element's bucket: the number of splitters at or below it
+        let buckets: Vec<usize> = (0..len)
+            .map(|i| {
+                let (mut lo, mut hi) = (0, splitters.len());
+                while lo < hi {
+                    let mid = (lo + hi) / 2;
+                    if ops.lt(&items[i], &items[splitters[mid]]) {
+                        hi = mid;
+                    } else {
+                        lo = mid + 1;
+                    }
+                }
+                lo
+            })
+            .collect();
+
+        // Stably scatter the indices into bucket-order, via a running (exclusive) sum of bucket sizes
+        let mut offsets = vec![0; splitters.len() + 2];
+        for &b in buckets.iter() {
+            offsets[b + 1] += 1;
+        }
+        for b in 1..offsets.len() {
+            offsets[b] += offsets[b - 1];
+        }
+        let bounds = offsets.clone();
+        let mut order = vec![0; len];
+        for (i, &b) in buckets.iter().enumerate() {
+            order[offsets[b]] = i;
+            offsets[b] += 1;
+        }
+        let mut dest = vec![0; len];
+        MergeSort::permute(items, &order, &mut dest, ops);
+
+        // And sort each bucket
+        for pair in bounds.windows(2) {
+            let bucket = &mut items[pair[0]..pair[1]];
+            if bucket.len() == len {
+                MergeSort::sort_with(bucket, ops); // No progress. Fall back to merge sort.
+            } else {
+                Self::sort_with(bucket, ops);
+            }
+        }
+    }
+}
+impl BucketSort {
+    /// Slice-length at and below which [InsertionSort] is used instead
+    const INSERTION_THRESHOLD: usize = 32;
+
+    /// Get the indices of the splitters of `items`, sorted and with duplicates removed.
+    /// Samples roughly `sqrt(n)` evenly-spaced positions.
+    fn splitters<T, F, I>(items: &[T], ops: &mut Ops<'_, F, I>) -> Vec<usize>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        I: Instrument,
+    {
+        let len = items.len();
+        let mut nbuckets = 1;
+        while nbuckets * nbuckets < len {
+            nbuckets += 1;
+        }
+        let step = len / nbuckets;
+        let mut sample: Vec<usize> = (1..nbuckets).map(|b| b * step).collect();
+        // Insertion sort the sample-indices by their elements
+        for partition in 1..sample.len() {
+            let mut i = partition;
+            while i > 0 && ops.lt(&items[sample[i]], &items[sample[i - 1]]) {
+                sample.swap(i - 1, i);
+                i -= 1;
+            }
+        }
+        let mut splitters: Vec<usize> = Vec::with_capacity(sample.len());
+        for idx in sample {
+            match splitters.last() {
+                Some(&last) if !ops.lt(&items[last], &items[idx]) => (), // Duplicate
+                _ => splitters.push(idx),
+            }
+        }
+        splitters
+    }
+}
+
+#[test]
+fn test_bucket() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut items = vec![10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5];
+    BucketSort::sort(&mut items);
+    assert_eq!(items, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+    // Splitters all equal, with larger elements after them
+    let mut items: Vec<i32> = (0..200)
+        .map(|i| if i % 50 == 49 { 200 - i } else { 5 })
+        .collect();
+    let mut correct = items.clone();
+    StdLibSort::sort(&mut correct);
+    BucketSort::sort(&mut items);
+    assert_eq!(items, correct);
+
+    // Random inputs, with varying numbers of duplicates, checked against the standard library
+    let mut rng = StdRng::seed_from_u64(30);
+    for &(len, range) in [(33, 5), (500, 1_000_000), (2_000, 20), (5_000, 5_000)].iter() {
+        let items: Vec<(i32, usize)> = (0..len).map(|i| (rng.gen_range(0..range), i)).collect();
+        let mut correct = items.clone();
+        StdLibSort::sort_by_key(&mut correct, |p| p.0);
+
+        let mut bucket = items.clone();
+        BucketSort::sort_by_key(&mut bucket, |p| p.0);
+        assert_eq!(bucket, correct); // Stable, so identical
+    }
+}
+
+///
+/// # Radix-Sortable Keys
+///
+/// Maps integer keys onto `u64`, preserving their order.
+/// Signed integers have their sign-bit flipped, so that negative values sort first.
+///
+pub trait RadixKey: Copy {
+    fn radix_key(self) -> u64;
+}
+macro_rules! radix_key_unsigned {
+    ($($t:ty),*) => {$(
+        impl RadixKey for $t {
+            fn radix_key(self) -> u64 {
+                self as u64
+            }
+        }
+    )*};
+}
+macro_rules! radix_key_signed {
+    ($($t:ty),*) => {$(
+        impl RadixKey for $t {
+            fn radix_key(self) -> u64 {
+                (self as i64 as u64) ^ (1 << 63)
+            }
+        }
+    )*};
+}
+radix_key_unsigned!(u8, u16, u32, u64, usize);
+radix_key_signed!(i8, i16, i32, i64, isize);
+
+/// Pair of a [RadixKey] value and its element's original index
+type Keyed = (u64, usize);
+
+/// Extract the [RadixKey] of each element of `items`, paired with its index
+fn keyed<T, K: RadixKey, F: FnMut(&T) -> K>(items: &[T], mut key: F) -> Vec<Keyed> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(idx, t)| (key(t).radix_key(), idx))
+        .collect()
+}
+
+/// Re-arrange `items` into the order listed in `sorted`, whose second entries are original indices.
+/// Follows each cycle of the permutation, swapping elements directly into place.
+fn apply_order<T>(items: &mut [T], sorted: &[Keyed]) {
+    // Invert the order, to get the destination of each element
+    let mut dest = vec![0; items.len()];
+    for (pos, &(_, idx)) in sorted.iter().enumerate() {
+        dest[idx] = pos;
+    }
+    for i in 0..items.len() {
+        while dest[i] != i {
+            let d = dest[i];
+            items.swap(i, d);
+            dest.swap(i, d);
+        }
+    }
+}
+
+///
+/// # Counting Sort
+///
+/// Counts occurrences of each key in the range between the minimum and maximum,
+/// then computes each element's destination from the running totals.
+/// Runs in O(n + k) time and space, for key-range `k`, so is best suited to densely-packed keys.
+/// Where `k` exceeds a small multiple of `n`, falls back to [RadixSort]'s LSD passes instead.
+///
+/// Stable: equal keys retain their relative order.
+///
+pub struct CountingSort;
+impl CountingSort {
+    /// Largest key-range counted directly, as a multiple of the number of items
+    const MAX_RANGE_FACTOR: usize = 4;
+
+    /// Sort `items` in ascending order
+    pub fn sort<T: RadixKey>(items: &mut [T]) {
+        Self::sort_by_key(items, |&t| t)
+    }
+    /// Sort `items` by the integer keys extracted by function `key`
+    pub fn sort_by_key<T, K: RadixKey, F: FnMut(&T) -> K>(items: &mut [T], key: F) {
+        let keys = keyed(items, key);
+        let (min, max) = match (
+            keys.iter().map(|k| k.0).min(),
+            keys.iter().map(|k| k.0).max(),
+        ) {
+            (Some(min), Some(max)) => (min, max),
+            _ => return, // Empty
+        };
+        // Check the key-range is small enough to count, without overflow
+        let range = (max - min)
+            .checked_add(1)
+            .and_then(|r| usize::try_from(r).ok())
+            .filter(|&r| r <= keys.len().saturating_mul(Self::MAX_RANGE_FACTOR).max(256));
+        let range = match range {
+            Some(range) => range,
+            None => return apply_order(items, &RadixSort::lsd(keys)), // Too sparse
+        };
+        // Count up each key
+        let mut counts = vec![0usize; range];
+        for &(k, _) in keys.iter() {
+            counts[(k - min) as usize] += 1;
+        }
+        // Convert to starting positions, via a running (exclusive) sum
+        let mut total = 0;
+        for count in counts.iter_mut() {
+            let c = *count;
+            *count = total;
+            total += c;
+        }
+        // And place each key, in order
+        let mut sorted = vec![(0, 0); keys.len()];
+        for &(k, idx) in keys.iter() {
+            let pos = &mut counts[(k - min) as usize];
+            sorted[*pos] = (k, idx);
+            *pos += 1;
+        }
+        apply_order(items, &sorted)
+    }
+}
+
+#[test]
+fn test_counting() {
+    let mut items = vec![10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5];
+    CountingSort::sort(&mut items);
+    assert_eq!(items, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+    let mut items = vec![-3i8, 127, -128, 0, 5, -3];
+    CountingSort::sort(&mut items);
+    assert_eq!(items, &[-128, -3, -3, 0, 5, 127]);
+
+    // Extreme key-ranges fall back to radix sort, rather than overflowing or over-allocating
+    let mut items = vec![i64::MIN, i64::MAX, 0];
+    CountingSort::sort(&mut items);
+    assert_eq!(items, &[i64::MIN, 0, i64::MAX]);
+
+    let mut items = vec![u32::MAX, 0u32, 7, u32::MAX, 7];
+    CountingSort::sort(&mut items);
+    assert_eq!(items, &[0, 7, 7, u32::MAX, u32::MAX]);
+
+    let mut items = vec![(u64::MAX, 0), (0u64, 1), (u64::MAX, 2), (0, 3)];
+    CountingSort::sort_by_key(&mut items, |p| p.0);
+    assert_eq!(items, &[(0, 1), (0, 3), (u64::MAX, 0), (u64::MAX, 2)]);
+}
+
+///
+/// # Radix Sort
+///
+/// Byte-at-a-time radix sorts over [RadixKey]s, in both directions:
+/// * `sort_lsd` makes passes from the least-significant byte upward, skipping any in which all keys agree.
+/// * `sort_msd` partitions on the most-significant byte, then recurses into each bucket,
+///   finishing small buckets with insertion sort.
+///
+/// Both sort (key, index) pairs into a scratch buffer, then permute `items` into place.
+/// Both are stable: equal keys retain their relative order.
+///
+pub struct RadixSort;
+impl RadixSort {
+    /// Bucket-size at and below which `sort_msd` switches to insertion sort
+    const MSD_INSERTION_THRESHOLD: usize = 32;
+
+    /// Sort `items` in ascending order, least-significant byte first
+    pub fn sort_lsd<T: RadixKey>(items: &mut [T]) {
+        Self::sort_lsd_by_key(items, |&t| t)
+    }
+    /// Sort `items` by the keys extracted by `key`, least-significant byte first
+    pub fn sort_lsd_by_key<T, K: RadixKey, F: FnMut(&T) -> K>(items: &mut [T], key: F) {
+        let keys = keyed(items, key);
+        apply_order(items, &Self::lsd(keys))
+    }
+    /// Helper for `sort_lsd`, and for [CountingSort]'s fallback. Sorts and returns the (key, index) pairs.
+    fn lsd(mut keys: Vec<Keyed>) -> Vec<Keyed> {
+        let mut buf = vec![(0, 0); keys.len()];
+        for shift in (0..64).step_by(8) {
+            let counts = Self::counts(&keys, shift);
+            if counts.contains(&keys.len()) {
+                continue; // All keys share this byte. Skip it.
+            }
+            Self::scatter(&keys, &mut buf, counts, shift);
+            std::mem::swap(&mut keys, &mut buf);
+        }
+        keys
+    }
+    /// Sort `items` in ascending order, most-significant byte first
+    pub fn sort_msd<T: RadixKey>(items: &mut [T]) {
+        Self::sort_msd_by_key(items, |&t| t)
+    }
+    /// Sort `items` by the keys extracted by `key`, most-significant byte first
+    pub fn sort_msd_by_key<T, K: RadixKey, F: FnMut(&T) -> K>(items: &mut [T], key: F) {
+        let mut keys = keyed(items, key);
+        let mut buf = vec![(0, 0); keys.len()];
+        Self::msd(&mut keys, &mut buf, 56);
+        apply_order(items, &keys)
+    }
+    /// Recursive helper for `sort_msd`, partitioning on the byte at `shift`
+    fn msd(keys: &mut [Keyed], buf: &mut [Keyed], shift: u32) {
+        if keys.len() <= Self::MSD_INSERTION_THRESHOLD {
+            // Insertion sort on the keys alone, retaining stability
+            for partition in 1..keys.len() {
+                let mut i = partition;
+                while i > 0 && keys[i].0 < keys[i - 1].0 {
+                    keys.swap(i - 1, i);
+                    i -= 1;
+                }
+            }
+            return;
+        }
+        let counts = Self::counts(keys, shift);
+        Self::scatter(keys, buf, counts, shift);
+        keys.copy_from_slice(buf);
+        if shift == 0 {
+            return; // Last byte. Done.
+        }
+        // Recursively sort each bucket on the next byte
+        let mut start = 0;
+        for count in counts.iter() {
+            let end = start + count;
+            if *count > 1 {
+                Self::msd(&mut keys[start..end], &mut buf[start..end], shift - 8);
+            }
+            start = end;
+        }
+    }
+    /// Count the occurrences of each value of the byte at `shift`
+    fn counts(keys: &[Keyed], shift: u32) -> [usize; 256] {
+        let mut counts = [0; 256];
+        for &(k, _) in keys.iter() {
+            counts[((k >> shift) & 0xff) as usize] += 1;
+        }
+        counts
+    }
+    /// Stably scatter `keys` into `dest`, bucketed by the byte at `shift`
+    fn scatter(keys: &[Keyed], dest: &mut [Keyed], counts: [usize; 256], shift: u32) {
+        let mut offsets = [0; 256];
+        let mut total = 0;
+        for (offset, count) in offsets.iter_mut().zip(counts.iter()) {
+            *offset = total;
+            total += count;
+        }
+        for &(k, idx) in keys.iter() {
+            let pos = &mut offsets[((k >> shift) & 0xff) as usize];
+            dest[*pos] = (k, idx);
+            *pos += 1;
+        }
+    }
+}
+
+#[test]
+fn test_radix() {
+    let mut items = vec![10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5];
+    RadixSort::sort_lsd(&mut items);
+    assert_eq!(items, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+    let mut items = vec![10, 0, 9, 1, 8, 2, 7, 3, 6, 4, 5];
+    RadixSort::sort_msd(&mut items);
+    assert_eq!(items, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+    let mut items = vec![i64::MAX, -1, i64::MIN, 0, 1 << 40, -(1 << 40)];
+    RadixSort::sort_lsd(&mut items);
+    assert_eq!(items, &[i64::MIN, -(1 << 40), -1, 0, 1 << 40, i64::MAX]);
+}
+
+#[test]
+fn test_integer_sorts_random() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(30);
+    for &len in [0, 1, 31, 33, 500, 2_000].iter() {
+        let items: Vec<i32> = (0..len).map(|_| rng.gen_range(-1_000..1_000)).collect();
+        let mut correct = items.clone();
+        StdLibSort::sort(&mut correct);
+
+        let mut counted = items.clone();
+        CountingSort::sort(&mut counted);
+        assert_eq!(counted, correct);
+
+        let mut lsd = items.clone();
+        RadixSort::sort_lsd(&mut lsd);
+        assert_eq!(lsd, correct);
+
+        let mut msd = items.clone();
+        RadixSort::sort_msd(&mut msd);
+        assert_eq!(msd, correct);
+
+        // Stability: sort (key, index) pairs by key alone, and compare with the (stable) standard library
+        let pairs: Vec<(i32, usize)> = items.iter().map(|k| k / 100).zip(0..).collect();
+        let mut correct = pairs.clone();
+        StdLibSort::sort_by_key(&mut correct, |p| p.0);
+
+        let mut counted = pairs.clone();
+        CountingSort::sort_by_key(&mut counted, |p| p.0);
+        assert_eq!(counted, correct);
+
+        let mut lsd = pairs.clone();
+        RadixSort::sort_lsd_by_key(&mut lsd, |p| p.0);
+        assert_eq!(lsd, correct);
+
+        let mut msd = pairs.clone();
+        RadixSort::sort_msd_by_key(&mut msd, |p| p.0);
+        assert_eq!(msd, correct);
+    }
+}
+
+#[test]
+fn test_hybrid_sorts() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    check_sorter::<ShellSort>();
+    check_sorter::<IntroSort>();
+    check_sorter::<TimSort>();
+    check_sorter::<BucketSort>();
+    check_stable::<TimSort>();
+    check_stable::<BucketSort>();
+
+    // Random inputs, with plenty of duplicates, checked against the standard library
+    let mut rng = StdRng::seed_from_u64(30);
+    for &len in [17, 100, 1_000, 5_000].iter() {
+        let items: Vec<(i32, usize)> = (0..len).map(|i| (rng.gen_range(0..50), i)).collect();
+        let mut correct = items.clone();
+        StdLibSort::sort_by_key(&mut correct, |p| p.0);
+
+        let mut tim = items.clone();
+        TimSort::sort_by_key(&mut tim, |p| p.0);
+        assert_eq!(tim, correct); // Stable, so identical
+
+        let mut bucket = items.clone();
+        BucketSort::sort_by_key(&mut bucket, |p| p.0);
+        assert_eq!(bucket, correct); // Also stable
+
+        let mut shell = items.clone();
+        ShellSort::sort_by_key(&mut shell, |p| p.0);
+        assert!(shell.iter().map(|p| p.0).eq(correct.iter().map(|p| p.0)));
+
+        let mut intro = items.clone();
+        IntroSort::sort_by_key(&mut intro, |p| p.0);
+        assert!(intro.iter().map(|p| p.0).eq(correct.iter().map(|p| p.0)));
+    }
+}
+
+///
+/// # Parallel Merge Sort
+///
+/// Multi-threaded variant of [MergeSort], which likewise sorts a permutation of indices.
+/// Each level of recursion sorts its two halves on separate scoped threads,
+/// splitting the thread-budget between them, until it is exhausted.
+/// Slices of length at most `cutoff` are finished with [InsertionSort].
+///
+/// Stable: equal elements retain their relative order.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelMergeSort {
+    /// Maximum number of threads, including the caller's
+    pub threads: usize,
+    /// Length at and below which slices are insertion-sorted
+    pub cutoff: usize,
+}
+impl Default for ParallelMergeSort {
+    /// Use all available parallelism
+    fn default() -> Self {
+        Self::new(available_threads())
+    }
+}
+impl ParallelMergeSort {
+    /// Create a new [ParallelMergeSort] using up to `threads` threads, with the default `cutoff`
+    pub fn new(threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+            cutoff: DEFAULT_PARALLEL_CUTOFF,
+        }
+    }
+    /// Sort `items` in ascending order
+    pub fn sort<T: Ord + Sync>(&self, items: &mut [T]) {
+        self.sort_by(items, T::cmp)
+    }
+    /// Sort `items` with comparator-function `cmp`
+    pub fn sort_by<T, F>(&self, items: &mut [T], cmp: F)
+    where
+        T: Sync,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        let mut buf = MergeBuffer::new();
+        buf.reset(items.len());
+        let MergeBuffer { order, scratch } = &mut buf;
+        self.mergesort(items, order, scratch, &cmp, self.threads);
+
+        // Moving `items` into place is sequential
+        let mut stats = NoStats;
+        MergeSort::permute(items, order, scratch, &mut Ops::new(&cmp, &mut stats));
+    }
+    /// Recursive helper, with `threads` remaining in its budget.
+    /// Sorts the indices in `a` by the `items` they refer to,
+    /// using `b` (which must initially equal `a`) as scratch-space.
+    fn mergesort<T, F>(
+        &self,
+        items: &[T],
+        a: &mut [usize],
+        b: &mut [usize],
+        cmp: &F,
+        threads: usize,
+    ) where
+        T: Sync,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        if a.len() <= self.cutoff.max(1) {
+            return InsertionSort::sort_by(a, |&i, &j| cmp(&items[i], &items[j]));
+        }
+        // Split each of `a` and `b` in halves
+        let mid = a.len() / 2;
+        let (a0, a1) = a.split_at_mut(mid);
+        let (b0, b1) = b.split_at_mut(mid);
+
+        // Merge-sort each half, swapping roles, and in parallel if we have threads to spare
+        if threads > 1 {
+            let left_threads = threads / 2;
+            std::thread::scope(|s| {
+                s.spawn(|| self.mergesort(items, b0, a0, cmp, left_threads));
+                self.mergesort(items, b1, a1, cmp, threads - left_threads);
+            });
+        } else {
+            self.mergesort(items, b0, a0, cmp, 1);
+            self.mergesort(items, b1, a1, cmp, 1);
+        }
+        // Merge the two halves of `b` into `a`
+        MergeSort::merge(b0, b1, a, |i, j| {
+            cmp(&items[i], &items[j]) == Ordering::Less
+        });
+    }
+}
+
+///
+/// # Parallel Quick Sort
+///
+/// Multi-threaded variant of [QuickSort].
+/// Each partitioning step hands its two sides to separate scoped threads,
+/// splitting the thread-budget between them in proportion to their lengths.
+/// Once the budget is exhausted, sides are sorted sequentially by [IntroSort],
+/// whose heap-sort fallback bounds the worst case at O(n log n).
+/// Slices of length at most `cutoff` are finished with [InsertionSort].
+///
+/// Not stable: equal elements may be re-ordered.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelQuickSort {
+    /// Maximum number of threads, including the caller's
+    pub threads: usize,
+    /// Length at and below which slices are insertion-sorted
+    pub cutoff: usize,
+}
+impl Default for ParallelQuickSort {
+    /// Use all available parallelism
+    fn default() -> Self {
+        Self::new(available_threads())
+    }
+}
+impl ParallelQuickSort {
+    /// Create a new [ParallelQuickSort] using up to `threads` threads, with the default `cutoff`
+    pub fn new(threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+            cutoff: DEFAULT_PARALLEL_CUTOFF,
+        }
+    }
+    /// Sort `items` in ascending order
+    pub fn sort<T: Ord + Send>(&self, items: &mut [T]) {
+        self.sort_by(items, T::cmp)
+    }
+    /// Sort `items` with comparator-function `cmp`
+    pub fn sort_by<T, F>(&self, items: &mut [T], cmp: F)
+    where
+        T: Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        self.quicksort(items, &cmp, self.threads)
+    }
+    /// Recursive helper, with `threads` remaining in its budget
+    fn quicksort<T, F>(&self, items: &mut [T], cmp: &F, threads: usize)
+    where
+        T: Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        if items.len() <= self.cutoff.max(1) {
+            return InsertionSort::sort_by(items, cmp);
+        }
+        if threads <= 1 {
+            return IntroSort::sort_by(items, cmp);
+        }
+        // Partition around a median-of-three pivot, setting aside all elements equal to it
+        let mut stats = NoStats;
+        let ops = &mut Ops::new(cmp, &mut stats);
+        let len = items.len();
+        let p = median_of_three(items, 0, len / 2, len - 1, ops);
+        ops.swap(items, 0, p);
+        let (lt, gt) = partition_three_way(items, ops);
+
+        // And sort each side on its own thread, splitting our budget in proportion to their lengths
+        let (left, rest) = items.split_at_mut(lt);
+        let right = &mut rest[gt - lt..];
+        let left_threads =
+            (threads * left.len() / (left.len() + right.len()).max(1)).clamp(1, threads - 1);
+        std::thread::scope(|s| {
+            s.spawn(|| self.quicksort(left, cmp, left_threads));
+            self.quicksort(right, cmp, threads - left_threads);
+        });
+    }
+}
+
+/// Default insertion-sort cutoff for the parallel sorts
+const DEFAULT_PARALLEL_CUTOFF: usize = 32;
+
+/// Get the number of available hardware threads, or one if unknown
+fn available_threads() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+#[test]
+fn test_parallel_sorts() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(33);
+    let inputs: Vec<Vec<(i32, usize)>> = vec![
+        vec![],
+        vec![(1, 0)],
+        (0..100_000).map(|i| (i, i as usize)).collect(),
+        (0..100_000).map(|i| (rng.gen_range(0..1_000), i)).collect(),
+        (0..100_000).map(|i| (rng.gen(), i)).collect(),
+    ];
+    for input in inputs.iter() {
+        let mut correct = input.clone();
+        StdLibSort::sort_by_key(&mut correct, |p| p.0);
+
+        for &threads in [1, 2, 3, 8].iter() {
+            // Merge sort is stable, so should match exactly
+            let mut items = input.clone();
+            ParallelMergeSort::new(threads).sort_by(&mut items, |a, b| a.0.cmp(&b.0));
+            assert_eq!(items, correct);
+
+            let mut items = input.clone();
+            ParallelQuickSort::new(threads).sort_by(&mut items, |a, b| a.0.cmp(&b.0));
+            assert!(items.iter().map(|p| p.0).eq(correct.iter().map(|p| p.0)));
+        }
+    }
+
+    // And with the default, all-available thread-counts
+    let mut items: Vec<i32> = (0..10_000).rev().collect();
+    ParallelMergeSort::default().sort(&mut items);
+    assert_eq!(items, (0..10_000).collect::<Vec<_>>());
+    let mut items: Vec<i32> = (0..10_000).rev().collect();
+    ParallelQuickSort::default().sort(&mut items);
+    assert_eq!(items, (0..10_000).collect::<Vec<_>>());
+}
@@ -0,0 +1,265 @@
+//!
+//! # Sorting Benchmarks
+//!
+//! Generates standard input [Distribution]s from a seeded RNG,
+//! and runs sorting algorithms over them, recording wall-clock time and operation counts.
+//!
+//! Intended for choosing among the algorithms of [crate::sort] given the shape of real data,
+//! e.g. whether it arrives nearly-sorted, or with few distinct keys.
+//! Timings are only meaningful in optimized (`--release`) builds.
+//!
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+// Local Imports
+use crate::sort::{SortStats, Sorter};
+
+///
+/// # Input Distribution
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distribution {
+    /// Uniformly random values
+    Random,
+    /// Already in ascending order
+    Sorted,
+    /// In descending order
+    Reversed,
+    /// Ascending through the first half, then descending
+    OrganPipe,
+    /// Random values drawn from only `distinct` possibilities
+    FewUnique { distinct: usize },
+    /// Sorted, then perturbed by `swaps` random pair-wise swaps
+    NearlySorted { swaps: usize },
+}
+impl Distribution {
+    /// Get the standard set of distributions for inputs of length `len`
+    pub fn standard(len: usize) -> Vec<Distribution> {
+        vec![
+            Distribution::Random,
+            Distribution::Sorted,
+            Distribution::Reversed,
+            Distribution::OrganPipe,
+            Distribution::FewUnique { distinct: 10 },
+            Distribution::NearlySorted {
+                swaps: (len / 100).max(1),
+            },
+        ]
+    }
+    /// Generate `len` values from this distribution, seeded by `seed`
+    pub fn generate(&self, len: usize, seed: u64) -> Vec<i64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let n = len as i64;
+        match *self {
+            Distribution::Random => (0..len).map(|_| rng.gen()).collect(),
+            Distribution::Sorted => (0..n).collect(),
+            Distribution::Reversed => (0..n).rev().collect(),
+            Distribution::OrganPipe => (0..n).map(|i| i.min(n - 1 - i)).collect(),
+            Distribution::FewUnique { distinct } => {
+                let distinct = distinct.max(1) as i64;
+                (0..len).map(|_| rng.gen_range(0..distinct)).collect()
+            }
+            Distribution::NearlySorted { swaps } => {
+                let mut items: Vec<i64> = (0..n).collect();
+                if len > 1 {
+                    for _ in 0..swaps {
+                        let a = rng.gen_range(0..len);
+                        let b = rng.gen_range(0..len);
+                        items.swap(a, b);
+                    }
+                }
+                items
+            }
+        }
+    }
+}
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Distribution::Random => write!(f, "random"),
+            Distribution::Sorted => write!(f, "sorted"),
+            Distribution::Reversed => write!(f, "reversed"),
+            Distribution::OrganPipe => write!(f, "organ-pipe"),
+            Distribution::FewUnique { distinct } => write!(f, "few-unique({})", distinct),
+            Distribution::NearlySorted { swaps } => write!(f, "nearly-sorted({})", swaps),
+        }
+    }
+}
+
+///
+/// # Measurement
+///
+/// Result of running a single algorithm over a single input distribution.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub algorithm: String,
+    pub distribution: Distribution,
+    pub len: usize,
+    /// Fastest time among all repeats
+    pub elapsed: Duration,
+    /// Operation counts. All zero for algorithms run without instrumentation.
+    pub stats: SortStats,
+}
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<24} {:<20} {:>10} {:>12.3?} {:>14} {:>14}",
+            self.algorithm,
+            self.distribution.to_string(),
+            self.len,
+            self.elapsed,
+            self.stats.comparisons,
+            self.stats.swaps,
+        )
+    }
+}
+
+///
+/// # Benchmark
+///
+/// Configuration for running algorithms over each of a set of input [Distribution]s.
+/// Each distribution is generated once per run, from `seed`, so that all algorithms see identical inputs.
+///
+#[derive(Debug, Clone)]
+pub struct Bench {
+    /// Input length
+    pub len: usize,
+    /// RNG seed
+    pub seed: u64,
+    /// Number of timed runs per input. The fastest is reported.
+    pub repeats: usize,
+    /// Input distributions
+    pub distributions: Vec<Distribution>,
+}
+impl Bench {
+    /// Create a new [Bench] over the [Distribution::standard] set, of inputs of length `len`
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            seed: 0,
+            repeats: 1,
+            distributions: Distribution::standard(len),
+        }
+    }
+    /// Run [Sorter] `S` over each distribution, counting its operations
+    pub fn run<S: Sorter<i64>>(&self) -> Vec<Measurement> {
+        let name = std::any::type_name::<S>();
+        let name = name.rsplit("::").next().unwrap_or(name);
+        self.run_with(name, S::sort_counted)
+    }
+    /// Run sorting-function `sort` over each distribution, under name `name`.
+    /// Accepts algorithms outside the [Sorter] trait, e.g. the radix and parallel sorts;
+    /// those without instrumentation can return [SortStats::default].
+    ///
+    /// Panics if `sort` fails to sort its input.
+    pub fn run_with<F>(&self, name: &str, mut sort: F) -> Vec<Measurement>
+    where
+        F: FnMut(&mut [i64]) -> SortStats,
+    {
+        self.distributions
+            .iter()
+            .map(|&distribution| {
+                let input = distribution.generate(self.len, self.seed);
+                let mut elapsed = Duration::MAX;
+                let mut stats = SortStats::default();
+                for _ in 0..self.repeats.max(1) {
+                    let mut items = input.clone();
+                    let start = Instant::now();
+                    stats = sort(&mut items);
+                    elapsed = elapsed.min(start.elapsed());
+                    assert!(
+                        items.windows(2).all(|w| w[0] <= w[1]),
+                        "{} failed to sort {} input",
+                        name,
+                        distribution
+                    );
+                }
+                Measurement {
+                    algorithm: name.to_string(),
+                    distribution,
+                    len: self.len,
+                    elapsed,
+                    stats,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Format `measurements` as a table, one row per measurement
+pub fn report(measurements: &[Measurement]) -> String {
+    let mut s = format!(
+        "{:<24} {:<20} {:>10} {:>12} {:>14} {:>14}\n",
+        "algorithm", "distribution", "len", "time", "comparisons", "swaps"
+    );
+    for m in measurements.iter() {
+        s.push_str(&m.to_string());
+        s.push('\n');
+    }
+    s
+}
+
+#[test]
+fn test_distributions() {
+    let len = 100;
+    assert_eq!(
+        Distribution::Sorted.generate(len, 0),
+        (0..100).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Distribution::Reversed.generate(len, 0),
+        (0..100).rev().collect::<Vec<_>>()
+    );
+    assert_eq!(Distribution::OrganPipe.generate(5, 0), vec![0, 1, 2, 1, 0]);
+
+    let few = Distribution::FewUnique { distinct: 3 }.generate(len, 1);
+    assert!(few.iter().all(|&v| (0..3).contains(&v)));
+
+    // At most two displaced elements per swap
+    let nearly = Distribution::NearlySorted { swaps: 5 }.generate(len, 2);
+    let displaced = nearly.iter().zip(0..).filter(|&(&v, i)| v != i).count();
+    assert!(displaced <= 10);
+
+    // Seeded generation is reproducible
+    for dist in Distribution::standard(len) {
+        assert_eq!(dist.generate(len, 3), dist.generate(len, 3));
+        assert_eq!(dist.generate(len, 3).len(), len);
+    }
+    assert_ne!(
+        Distribution::Random.generate(len, 3),
+        Distribution::Random.generate(len, 4)
+    );
+}
+
+#[test]
+fn test_bench() {
+    use crate::sort::{InsertionSort, RadixSort, TimSort};
+
+    let bench = Bench::new(200);
+    let results = bench.run::<InsertionSort>();
+    assert_eq!(results.len(), bench.distributions.len());
+    assert_eq!(results[0].algorithm, "InsertionSort");
+
+    // Insertion sort makes a single comparison per element of sorted input, and no swaps
+    let sorted = &results[1];
+    assert_eq!(sorted.distribution, Distribution::Sorted);
+    assert_eq!(sorted.stats.comparisons, 199);
+    assert_eq!(sorted.stats.swaps, 0);
+    // And makes every possible swap for reversed input
+    assert_eq!(results[2].stats.swaps, 200 * 199 / 2);
+
+    let mut all = results;
+    all.extend(bench.run::<TimSort>());
+    all.extend(bench.run_with("RadixSort", |items| {
+        RadixSort::sort_lsd(items);
+        SortStats::default()
+    }));
+    let table = report(&all);
+    assert_eq!(table.lines().count(), 1 + 3 * bench.distributions.len());
+    assert!(table.contains("nearly-sorted(2)"));
+}
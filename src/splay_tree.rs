@@ -0,0 +1,186 @@
+//!
+//! # Splay Tree
+//!
+//! A self-adjusting [Balance] strategy for [BstMap]. Each access "splays" its node to the head,
+//! via a series of rotations which also roughly halve the depth of every node along its path.
+//! No per-node balance data is kept, and any single operation may take O(n) time,
+//! but any sequence of m splaying operations takes O(m log n) time: amortized O(log n) each.
+//! Every search made through mutable access splays the last node on its path, hit or miss:
+//! insertions (including of existing keys, and via [BstMap::entry]), removals (including of absent keys),
+//! and lookups via [BstMap::access], [BstMap::access_mut], and [BstMap::get_mut].
+//!
+//! Recently and frequently accessed keys stay near the head, making splay trees well-suited
+//! to skewed access patterns. Plain [BstMap::get] and the other immutable queries leave the tree as is,
+//! and so are not covered by the amortized bound: each costs time proportional to the current depth.
+//!
+
+use std::borrow::Borrow;
+
+// Local Imports
+use crate::binary_search_tree::{Balance, BstMap, NodeIndex};
+
+///
+/// # Splay Balance
+///
+/// Insertions splay the new node to the head. Removals splay the removed node's parent.
+/// Other searches through mutable access splay the last node visited.
+///
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Splay;
+impl Splay {
+    /// Splay node `idx` to the head of `tree`
+    fn splay<K, V>(tree: &mut BstMap<K, V, Self>, idx: NodeIndex) {
+        while let Some(parent) = tree[idx].parent {
+            let is_left = |tree: &BstMap<K, V, Self>, child, parent: NodeIndex| {
+                tree[parent].left == Some(child)
+            };
+            match tree[parent].parent {
+                None => {
+                    // Zig: `parent` is the head. Single rotation.
+                    Self::rotate_up(tree, idx, parent);
+                }
+                Some(grand) if is_left(tree, idx, parent) == is_left(tree, parent, grand) => {
+                    // Zig-zig: both links lean the same way. Rotate the grandparent first, then the parent.
+                    Self::rotate_up(tree, parent, grand);
+                    Self::rotate_up(tree, idx, parent);
+                }
+                Some(grand) => {
+                    // Zig-zag: the links lean opposite ways. Rotate `idx` up twice.
+                    Self::rotate_up(tree, idx, parent);
+                    Self::rotate_up(tree, idx, grand);
+                }
+            }
+        }
+    }
+    /// Rotate `idx` above its parent `parent`
+    fn rotate_up<K, V>(tree: &mut BstMap<K, V, Self>, idx: NodeIndex, parent: NodeIndex) {
+        if tree[parent].left == Some(idx) {
+            tree.rotate_right(parent);
+        } else {
+            tree.rotate_left(parent);
+        }
+    }
+}
+impl Balance for Splay {
+    type Meta = ();
+    fn leaf() {}
+    fn inserted<K, V>(tree: &mut BstMap<K, V, Self>, idx: NodeIndex) {
+        Self::splay(tree, idx);
+    }
+    fn accessed<K, V>(tree: &mut BstMap<K, V, Self>, idx: NodeIndex) {
+        Self::splay(tree, idx);
+    }
+    fn removed<K, V>(
+        tree: &mut BstMap<K, V, Self>,
+        parent: Option<NodeIndex>,
+        _child: Option<NodeIndex>,
+        _meta: (),
+    ) {
+        if let Some(p) = parent {
+            Self::splay(tree, p);
+        }
+    }
+    fn check<K: Ord + Clone, V>(_tree: &BstMap<K, V, Self>) -> bool {
+        true // No shape-invariants, beyond those of any BST
+    }
+}
+
+/// Splay-Tree Map
+pub type SplayMap<K, V> = BstMap<K, V, Splay>;
+
+impl<K: Ord, V> BstMap<K, V, Splay> {
+    /// Create a new, empty, [Splay]-balanced map
+    pub fn new_splay() -> Self {
+        Self::empty()
+    }
+    /// Get a reference to the value at `key`, if present.
+    /// Splays the found node, or the last node visited if `key` is absent, to the head.
+    pub fn access<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.access_mut(key).map(|v| &*v)
+    }
+    /// Get a mutable reference to the value at `key`, if present.
+    /// Splays the found node, or the last node visited if `key` is absent, to the head.
+    pub fn access_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get_mut(key)
+    }
+    /// Splay node `idx` to the head
+    pub fn splay(&mut self, idx: NodeIndex) {
+        Splay::splay(self, idx);
+    }
+}
+
+#[test]
+fn test_splay() {
+    use std::collections::BTreeMap;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(44);
+    let mut splay = SplayMap::new_splay();
+    let mut reference = BTreeMap::new();
+    for _ in 0..2000 {
+        let k: u16 = rng.gen_range(0..500);
+        match rng.gen_range(0..3) {
+            0 => assert_eq!(splay.remove(&k), reference.remove(&k)),
+            1 => assert_eq!(splay.access(&k), reference.get(&k)),
+            _ => assert_eq!(splay.insert(k, k), reference.insert(k, k)),
+        }
+    }
+    assert!(splay.check_invariants());
+    assert!(splay.iter().eq(reference.iter()));
+}
+
+#[test]
+fn test_splay_access() {
+    let mut splay = SplayMap::new_splay();
+    for k in 0..100 {
+        splay.insert(k, k.to_string());
+        // Each insertion lands at the head
+        assert_eq!(splay[splay.head.unwrap()].key, k);
+    }
+    // Sequential insertion leaves a path. Accessing its far end splays it to the head,
+    // and roughly halves the path's depth.
+    fn depth<K, V>(t: &SplayMap<K, V>, opt: Option<NodeIndex>) -> usize {
+        opt.map_or(0, |i| 1 + depth(t, t[i].left).max(depth(t, t[i].right)))
+    }
+    assert_eq!(depth(&splay, splay.head), 100);
+    assert_eq!(splay.access(&0).map(String::as_str), Some("0"));
+    assert_eq!(splay[splay.head.unwrap()].key, 0);
+    assert!(depth(&splay, splay.head) <= 52);
+
+    // Misses splay the last node visited
+    assert_eq!(splay.access(&1000), None);
+    assert_eq!(splay[splay.head.unwrap()].key, 99);
+
+    if let Some(v) = splay.access_mut(&50) {
+        v.push('!');
+    }
+    assert_eq!(splay[splay.head.unwrap()].value, "50!");
+    assert!(splay.check_invariants());
+}
+
+#[test]
+fn test_splay_repeated_operations() {
+    // Re-inserting an existing key, and removing an absent one, each splay their search path
+    let mut splay = SplayMap::new_splay();
+    for k in 0..100 {
+        splay.insert(k, ());
+    }
+    assert_eq!(splay.insert(0, ()), Some(()));
+    assert_eq!(splay[splay.head.unwrap()].key, 0);
+
+    assert_eq!(splay.remove(&1000), None);
+    assert_eq!(splay[splay.head.unwrap()].key, 99);
+
+    *splay.entry(50).or_insert(()) = ();
+    assert_eq!(splay[splay.head.unwrap()].key, 50);
+    assert!(splay.check_invariants());
+}
@@ -0,0 +1,349 @@
+//!
+//! # Treap
+//!
+//! A randomized [Balance] strategy for [BstMap]: each node draws a random priority on insertion,
+//! and the tree is kept in max-heap order by priority, as well as in search order by key.
+//! The resulting shape is that of a BST built by inserting keys in random order,
+//! with expected O(log n) depth regardless of the actual order of insertion.
+//!
+//! Treaps also support splitting a tree by key, and merging two key-ordered trees,
+//! each in expected O(log n) link-updates. These underlie [BstMap::split_off], [BstMap::append],
+//! and [BstMap::remove_range] for [TreapMap]s.
+//!
+//! Note each map owns its node-storage, so moving entries between maps also relocates their nodes.
+//! Splits relocate the smaller side.
+//!
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::ops::{Bound, RangeBounds};
+
+// Local Imports
+use crate::binary_search_tree::{Balance, BstMap, NodeIndex};
+
+///
+/// # Treap Balance
+///
+/// Node priorities are drawn uniformly at random, and every node's priority is at least those of its children.
+///
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Treap;
+impl Balance for Treap {
+    /// Random heap-priority
+    type Meta = u64;
+    fn leaf() -> u64 {
+        rand::random()
+    }
+    fn inserted<K, V>(tree: &mut BstMap<K, V, Self>, idx: NodeIndex) {
+        // Rotate the new node up until its parent out-ranks it
+        while let Some(parent) = tree[idx].parent {
+            if tree[parent].meta >= tree[idx].meta {
+                break;
+            }
+            if tree[parent].left == Some(idx) {
+                tree.rotate_right(parent);
+            } else {
+                tree.rotate_left(parent);
+            }
+        }
+    }
+    fn removed<K, V>(
+        _tree: &mut BstMap<K, V, Self>,
+        _parent: Option<NodeIndex>,
+        _child: Option<NodeIndex>,
+        _meta: u64,
+    ) {
+        // Nothing to do. Removal splices out a node with at most one child,
+        // lifting that child to a parent which already out-ranked it.
+        // Two-child removals trade entries, but not priorities, with their successor.
+    }
+    fn check<K: Ord + Clone, V>(tree: &BstMap<K, V, Self>) -> bool {
+        tree.nodes.iter().all(|node| {
+            [node.left, node.right]
+                .iter()
+                .flatten()
+                .all(|&c| tree[c].meta <= node.meta)
+        })
+    }
+}
+
+/// Treap-Balanced Map
+pub type TreapMap<K, V> = BstMap<K, V, Treap>;
+
+impl<K: Ord, V> BstMap<K, V, Treap> {
+    /// Create a new, empty, [Treap]-balanced map
+    pub fn new_treap() -> Self {
+        Self::empty()
+    }
+    /// Split the map in two at `key`.
+    /// Entries with keys greater than or equal to `key` are moved into the returned map;
+    /// those less than `key` remain.
+    pub fn split_off<Q>(&mut self, key: &Q) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (left, right) = self.split(self.head, &mut |k: &K| k.borrow() < key);
+        // Relocate whichever side is smaller. The other remains our head throughout.
+        if self.size_of(left) <= self.size_of(right) {
+            self.head = right;
+            let mut moved = self.extract(left);
+            std::mem::swap(self, &mut moved);
+            moved
+        } else {
+            self.head = left;
+            self.extract(right)
+        }
+    }
+    /// Move all entries of `other` into this map, leaving `other` empty.
+    /// Entries of `other` replace any of the same key.
+    ///
+    /// If the two maps' keys do not overlap, i.e. all of one map's keys are less than all of the other's,
+    /// the two are merged in expected O(log n) link-updates, plus relocation of `other`'s nodes.
+    /// Otherwise each of `other`'s entries is inserted in turn.
+    pub fn append(&mut self, other: &mut Self) {
+        let (max, other_min) = match (self.max(), other.min()) {
+            (None, _) => return std::mem::swap(self, other),
+            (_, None) => return,
+            (Some(max), Some(other_min)) => (max, other_min),
+        };
+        let (min, other_max) = (self.min().unwrap(), other.max().unwrap());
+        let before = self[max].key < other[other_min].key;
+        let after = other[other_max].key < self[min].key;
+        if !before && !after {
+            // Overlapping keys. Insert one at a time.
+            for node in std::mem::take(&mut other.nodes) {
+                self.insert(node.key, node.value);
+            }
+            other.head = None;
+            return;
+        }
+        // Relocate `other`'s nodes to the end of our storage, offsetting their indices
+        let offset = self.nodes.len();
+        let shift = |idx: Option<NodeIndex>| idx.map(|i| NodeIndex(i.0 + offset));
+        let other_head = shift(other.head.take());
+        for mut node in std::mem::take(&mut other.nodes) {
+            node.index = NodeIndex(node.index.0 + offset);
+            node.left = shift(node.left);
+            node.right = shift(node.right);
+            node.parent = shift(node.parent);
+            self.nodes.push(node);
+        }
+        self.head = if before {
+            self.merge(self.head, other_head)
+        } else {
+            self.merge(other_head, self.head)
+        };
+        self.set_parent(self.head, None);
+    }
+    /// Remove all entries with keys in `range`, returning them as a new map.
+    /// Requires expected O(log n) link-updates, plus relocation of the removed nodes.
+    pub fn remove_range<Q, R>(&mut self, range: R) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        // Split off everything before the range, then everything after it
+        let (before, rest) = self.split(self.head, &mut |k: &K| match range.start_bound() {
+            Bound::Unbounded => false,
+            Bound::Included(s) => k.borrow() < s,
+            Bound::Excluded(s) => k.borrow() <= s,
+        });
+        let (within, after) = self.split(rest, &mut |k: &K| match range.end_bound() {
+            Bound::Unbounded => true,
+            Bound::Included(e) => k.borrow() <= e,
+            Bound::Excluded(e) => k.borrow() < e,
+        });
+        // Re-join the two outer parts, and relocate the middle
+        self.head = self.merge(before, after);
+        self.set_parent(self.head, None);
+        self.extract(within)
+    }
+    /// Split sub-tree `root` in two: nodes whose keys satisfy `goes_left`, and those which do not.
+    /// `goes_left` must be monotone: true for all keys below some point, and false for all above it.
+    /// Returns the roots of the two parts, each with its parent-link cleared.
+    fn split<F>(&mut self, root: Option<NodeIndex>, goes_left: &mut F) -> SplitRoots
+    where
+        F: FnMut(&K) -> bool,
+    {
+        let idx = match root {
+            None => return (None, None),
+            Some(idx) => idx,
+        };
+        let (left, right) = if goes_left(&self[idx].key) {
+            // `idx` and its left sub-tree go left. Split its right sub-tree.
+            let (inner, right) = self.split(self[idx].right, goes_left);
+            self[idx].right = inner;
+            self.set_parent(inner, Some(idx));
+            (Some(idx), right)
+        } else {
+            // `idx` and its right sub-tree go right. Split its left sub-tree.
+            let (left, inner) = self.split(self[idx].left, goes_left);
+            self[idx].left = inner;
+            self.set_parent(inner, Some(idx));
+            (left, Some(idx))
+        };
+        self.update_size(idx);
+        self.set_parent(left, None);
+        self.set_parent(right, None);
+        (left, right)
+    }
+    /// Merge sub-trees `left` and `right`, all of whose keys are less than all of `right`'s.
+    /// Returns the merged root. Its parent-link is left to the caller.
+    fn merge(&mut self, left: Option<NodeIndex>, right: Option<NodeIndex>) -> Option<NodeIndex> {
+        let (l, r) = match (left, right) {
+            (None, other) | (other, None) => return other,
+            (Some(l), Some(r)) => (l, r),
+        };
+        // The higher-priority root stays on top
+        let root = if self[l].meta >= self[r].meta {
+            let inner = self.merge(self[l].right, Some(r));
+            self[l].right = inner;
+            self.set_parent(inner, Some(l));
+            l
+        } else {
+            let inner = self.merge(Some(l), self[r].left);
+            self[r].left = inner;
+            self.set_parent(inner, Some(r));
+            r
+        };
+        self.update_size(root);
+        Some(root)
+    }
+    /// Set the parent of optional node `opt`, if present
+    fn set_parent(&mut self, opt: Option<NodeIndex>, parent: Option<NodeIndex>) {
+        if let Some(idx) = opt {
+            self[idx].parent = parent;
+        }
+    }
+    /// Move the (detached) sub-tree at `root` out of our storage, and into a new map.
+    /// All remaining nodes must be linked from our head.
+    fn extract(&mut self, root: Option<NodeIndex>) -> Self {
+        let mut map = Self::empty();
+        let root = match root {
+            None => return map,
+            Some(r) => r,
+        };
+        // Collect the sub-tree's members, and assign each a slot in the new map
+        let mut members = Vec::new();
+        let mut stack = vec![root];
+        while let Some(idx) = stack.pop() {
+            members.push(idx);
+            stack.extend([self[idx].left, self[idx].right].iter().flatten());
+        }
+        let slots: HashMap<usize, NodeIndex> = members
+            .iter()
+            .enumerate()
+            .map(|(slot, idx)| (idx.0, NodeIndex(slot)))
+            .collect();
+
+        // Take them in descending index-order, so that the nodes each removal moves are never members
+        members.sort_unstable_by_key(|idx| std::cmp::Reverse(idx.0));
+        let mut taken: Vec<_> = members
+            .into_iter()
+            .map(|idx| (slots[&idx.0], self.take_node(idx)))
+            .collect();
+        taken.sort_unstable_by_key(|(slot, _)| slot.0);
+
+        let relink = |opt: Option<NodeIndex>| opt.map(|idx| slots[&idx.0]);
+        for (slot, mut node) in taken {
+            node.index = slot;
+            node.left = relink(node.left);
+            node.right = relink(node.right);
+            node.parent = relink(node.parent);
+            map.nodes.push(node);
+        }
+        map.head = Some(NodeIndex(0));
+        map
+    }
+}
+
+// Roots of the two halves of a split
+type SplitRoots = (Option<NodeIndex>, Option<NodeIndex>);
+
+#[test]
+fn test_treap() {
+    use std::collections::BTreeMap;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(44);
+    let mut treap = TreapMap::new_treap();
+    let mut reference = BTreeMap::new();
+    for _ in 0..2000 {
+        let k: u16 = rng.gen_range(0..500);
+        if rng.gen_bool(0.3) {
+            assert_eq!(treap.remove(&k), reference.remove(&k));
+        } else {
+            assert_eq!(treap.insert(k, k), reference.insert(k, k));
+        }
+    }
+    assert!(treap.check_invariants());
+    assert!(treap.iter().eq(reference.iter()));
+
+    // Sorted insertion, the worst case for an unbalanced tree, stays shallow
+    let mut sorted = TreapMap::new_treap();
+    for k in 0..4096 {
+        sorted.insert(k, ());
+    }
+    fn depth<K, V>(t: &TreapMap<K, V>, opt: Option<NodeIndex>) -> usize {
+        opt.map_or(0, |i| 1 + depth(t, t[i].left).max(depth(t, t[i].right)))
+    }
+    assert!(sorted.check_invariants());
+    assert!(depth(&sorted, sorted.head) < 50);
+}
+
+#[test]
+fn test_treap_split_merge() {
+    let mut treap = TreapMap::new_treap();
+    for k in 0..100 {
+        treap.insert(k, k * 2);
+    }
+    // Split off the larger half, and the smaller
+    let mut upper = treap.split_off(&30);
+    assert!(treap.check_invariants() && upper.check_invariants());
+    assert_eq!(
+        treap.keys().copied().collect::<Vec<_>>(),
+        (0..30).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        upper.keys().copied().collect::<Vec<_>>(),
+        (30..100).collect::<Vec<_>>()
+    );
+    let top = upper.split_off(&90);
+    assert_eq!(upper.len(), 60);
+    assert_eq!(top.len(), 10);
+    assert_eq!(top.get(&95), Some(&190));
+
+    // Merge them back, in either order
+    let mut top = top;
+    top.append(&mut upper);
+    assert!(upper.is_empty());
+    treap.append(&mut top);
+    assert!(treap.check_invariants());
+    assert_eq!(
+        treap.keys().copied().collect::<Vec<_>>(),
+        (0..100).collect::<Vec<_>>()
+    );
+
+    // Overlapping keys are inserted, with `other`'s values winning
+    let mut other = TreapMap::new_treap();
+    other.insert(50, -1);
+    other.insert(150, -1);
+    treap.append(&mut other);
+    assert!(treap.check_invariants());
+    assert_eq!(treap.len(), 101);
+    assert_eq!(treap.get(&50), Some(&-1));
+
+    // Range deletion
+    let removed = treap.remove_range(10..=89);
+    assert!(treap.check_invariants() && removed.check_invariants());
+    assert_eq!(removed.len(), 80);
+    assert_eq!(treap.len(), 21);
+    assert_eq!(treap.range(..).next_back(), Some((&150, &-1)));
+    assert!(treap.get(&10).is_none() && treap.get(&90).is_some());
+    assert_eq!(treap.remove_range(200..).len(), 0);
+    assert_eq!(treap.remove_range(..).len(), 21);
+    assert!(treap.is_empty() && treap.check_invariants());
+}
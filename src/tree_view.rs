@@ -0,0 +1,397 @@
+//!
+//! # Binary Tree Views
+//!
+//! [TreeView] is a read-only view of a binary tree's shape and data,
+//! shared by the pointer-based [BinaryTree](crate::binary_tree::BinaryTree),
+//! the arena-based [ArenaTree](crate::arena_tree::ArenaTree),
+//! and the index-based [BstMap](crate::binary_search_tree::BstMap).
+//! The `chapter_04` tree algorithms accept any [TreeView].
+//!
+//! Each implementation names its nodes with its own handle type, e.g. a pointer or an index.
+//! Traversals are available as lazy iterators in pre-, in-, post-, and level-order,
+//! each yielding node handles rather than data. Data is accessed via [TreeView::data].
+//!
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::Deref;
+
+///
+/// # Binary Tree View
+///
+/// Implementors provide access to the head, each node's children, and each node's data.
+/// All other methods are provided in terms of these.
+///
+pub trait TreeView {
+    /// Node data type
+    type Data;
+    /// Node handle type. Compares by identity, not by data.
+    type Node: Clone + Eq;
+    /// Reference to node data, e.g. a plain `&Data`, or a guard of a `RefCell`
+    type DataRef<'a>: Deref<Target = Self::Data>
+    where
+        Self: 'a;
+
+    /// Get the head node, if the tree is non-empty
+    fn root(&self) -> Option<Self::Node>;
+    /// Get the left child of `node`
+    fn left(&self, node: &Self::Node) -> Option<Self::Node>;
+    /// Get the right child of `node`
+    fn right(&self, node: &Self::Node) -> Option<Self::Node>;
+    /// Get a reference to the data of `node`
+    fn data<'a>(&'a self, node: &'a Self::Node) -> Self::DataRef<'a>;
+
+    /// Boolean indication of whether `node` has no children
+    fn is_leaf(&self, node: &Self::Node) -> bool {
+        self.left(node).is_none() && self.right(node).is_none()
+    }
+    /// Get a pre-order iterator: each node, then its left sub-tree, then its right
+    fn iter_preorder(&self) -> PreOrder<'_, Self> {
+        PreOrder {
+            tree: self,
+            stack: self.root().into_iter().collect(),
+        }
+    }
+    /// Get an in-order iterator: each node's left sub-tree, then the node, then its right sub-tree
+    fn iter_inorder(&self) -> InOrder<'_, Self> {
+        let mut iter = InOrder {
+            tree: self,
+            stack: Vec::new(),
+        };
+        iter.push_left(self.root());
+        iter
+    }
+    /// Get a post-order iterator: each node's left sub-tree, then its right, then the node
+    fn iter_postorder(&self) -> PostOrder<'_, Self> {
+        PostOrder {
+            tree: self,
+            stack: self.root().map(|h| (h, false)).into_iter().collect(),
+        }
+    }
+    /// Get a level-order (breadth-first) iterator, visiting each level from left to right
+    fn iter_level_order(&self) -> LevelOrder<'_, Self> {
+        LevelOrder {
+            tree: self,
+            queue: self.root().into_iter().collect(),
+        }
+    }
+    /// Get an iterator over each level of the tree, from the head down.
+    /// Each level is a vector of its nodes, from left to right.
+    fn levels(&self) -> Levels<'_, Self> {
+        Levels {
+            tree: self,
+            level: self.root().into_iter().collect(),
+        }
+    }
+    /// Get an iterator over the leaves, the nodes without children, from left to right
+    fn leaves(&self) -> Leaves<'_, Self> {
+        Leaves(self.iter_preorder())
+    }
+    /// Get the number of nodes
+    fn len(&self) -> usize {
+        self.iter_preorder().count()
+    }
+    /// Boolean indication of whether the tree is empty
+    fn is_empty(&self) -> bool {
+        self.root().is_none()
+    }
+    /// Get the depth, the number of levels. Zero for an empty tree.
+    fn depth(&self) -> usize {
+        self.levels().count()
+    }
+    /// Serialize to level-order text, e.g. `[1,2,3,null,4]`, in which `null` marks each absent child,
+    /// and trailing `null`s are dropped.
+    fn serialize(&self) -> String
+    where
+        Self::Data: fmt::Display,
+    {
+        let mut tokens = Vec::new();
+        let mut queue: VecDeque<Option<Self::Node>> = VecDeque::new();
+        queue.push_back(self.root());
+        while let Some(opt) = queue.pop_front() {
+            match opt {
+                None => tokens.push(String::from("null")),
+                Some(node) => {
+                    tokens.push(self.data(&node).to_string());
+                    queue.push_back(self.left(&node));
+                    queue.push_back(self.right(&node));
+                }
+            }
+        }
+        // Drop trailing `null`s, the absent children of the last level
+        while tokens.last().is_some_and(|t| t == "null") {
+            tokens.pop();
+        }
+        format!("[{}]", tokens.join(","))
+    }
+    /// Render an ASCII-art drawing, with each node's data centered above the branches to its children:
+    ///
+    /// ```text
+    ///   _1
+    ///  /  \
+    ///  2  3
+    /// / \
+    /// 4 5
+    /// ```
+    ///
+    fn render(&self) -> String
+    where
+        Self::Data: fmt::Display,
+    {
+        let block = match self.root() {
+            None => return String::new(),
+            Some(root) => Block::render(self, &root),
+        };
+        let mut lines: Vec<&str> = block.lines.iter().map(|l| l.trim_end()).collect();
+        // Drop any blank trailing lines, e.g. from empty labels
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+        lines.join("\n")
+    }
+}
+
+///
+/// # Pre-Order Iterator
+///
+/// Holds a stack of nodes pending visit. Right children are pushed before left, so the left pops first.
+///
+pub struct PreOrder<'t, V: TreeView + ?Sized> {
+    tree: &'t V,
+    stack: Vec<V::Node>,
+}
+impl<'t, V: TreeView + ?Sized> Iterator for PreOrder<'t, V> {
+    type Item = V::Node;
+    fn next(&mut self) -> Option<V::Node> {
+        let node = self.stack.pop()?;
+        self.stack.extend(self.tree.right(&node));
+        self.stack.extend(self.tree.left(&node));
+        Some(node)
+    }
+}
+
+///
+/// # In-Order Iterator
+///
+/// Holds a stack of nodes whose left sub-trees are in progress.
+///
+pub struct InOrder<'t, V: TreeView + ?Sized> {
+    tree: &'t V,
+    stack: Vec<V::Node>,
+}
+impl<'t, V: TreeView + ?Sized> InOrder<'t, V> {
+    /// Push `opt` and its chain of left descendants
+    fn push_left(&mut self, mut opt: Option<V::Node>) {
+        while let Some(node) = opt {
+            opt = self.tree.left(&node);
+            self.stack.push(node);
+        }
+    }
+}
+impl<'t, V: TreeView + ?Sized> Iterator for InOrder<'t, V> {
+    type Item = V::Node;
+    fn next(&mut self) -> Option<V::Node> {
+        let node = self.stack.pop()?;
+        self.push_left(self.tree.right(&node));
+        Some(node)
+    }
+}
+
+///
+/// # Post-Order Iterator
+///
+/// Holds a stack of nodes, each flagged with whether its children have already been stacked.
+///
+pub struct PostOrder<'t, V: TreeView + ?Sized> {
+    tree: &'t V,
+    stack: Vec<(V::Node, bool)>,
+}
+impl<'t, V: TreeView + ?Sized> Iterator for PostOrder<'t, V> {
+    type Item = V::Node;
+    fn next(&mut self) -> Option<V::Node> {
+        loop {
+            let (node, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(node);
+            }
+            // Re-stack the node beneath its children, which are visited first
+            let (left, right) = (self.tree.left(&node), self.tree.right(&node));
+            self.stack.push((node, true));
+            self.stack.extend(right.map(|r| (r, false)));
+            self.stack.extend(left.map(|l| (l, false)));
+        }
+    }
+}
+
+///
+/// # Level-Order Iterator
+///
+/// Breadth-first, via a queue of nodes pending visit.
+///
+pub struct LevelOrder<'t, V: TreeView + ?Sized> {
+    tree: &'t V,
+    queue: VecDeque<V::Node>,
+}
+impl<'t, V: TreeView + ?Sized> Iterator for LevelOrder<'t, V> {
+    type Item = V::Node;
+    fn next(&mut self) -> Option<V::Node> {
+        let node = self.queue.pop_front()?;
+        self.queue.extend(self.tree.left(&node));
+        self.queue.extend(self.tree.right(&node));
+        Some(node)
+    }
+}
+
+///
+/// # Levels Iterator
+///
+/// Yields each level of nodes in turn, holding the next.
+///
+pub struct Levels<'t, V: TreeView + ?Sized> {
+    tree: &'t V,
+    level: Vec<V::Node>,
+}
+impl<'t, V: TreeView + ?Sized> Iterator for Levels<'t, V> {
+    type Item = Vec<V::Node>;
+    fn next(&mut self) -> Option<Vec<V::Node>> {
+        if self.level.is_empty() {
+            return None;
+        }
+        let tree = self.tree;
+        let next = self
+            .level
+            .iter()
+            .flat_map(|node| tree.left(node).into_iter().chain(tree.right(node)))
+            .collect();
+        Some(std::mem::replace(&mut self.level, next))
+    }
+}
+
+///
+/// # Leaves Iterator
+///
+/// Filters a [PreOrder] traversal down to its childless nodes.
+///
+pub struct Leaves<'t, V: TreeView + ?Sized>(PreOrder<'t, V>);
+impl<'t, V: TreeView + ?Sized> Iterator for Leaves<'t, V> {
+    type Item = V::Node;
+    fn next(&mut self) -> Option<V::Node> {
+        let tree = self.0.tree;
+        self.0.find(|node| tree.is_leaf(node))
+    }
+}
+
+///
+/// # Rendered Sub-Tree
+///
+/// A rectangular block of text lines, each `width` characters wide,
+/// with the sub-tree's head centered at column `middle` of its first line.
+///
+#[derive(Debug)]
+struct Block {
+    lines: Vec<String>,
+    width: usize,
+    middle: usize,
+}
+impl Block {
+    /// Recursively render the sub-tree at `node`
+    fn render<V>(tree: &V, node: &V::Node) -> Self
+    where
+        V: TreeView + ?Sized,
+        V::Data: fmt::Display,
+    {
+        // Empty labels are drawn as a single space, so that every block is at least one column wide
+        let label = match tree.data(node).to_string() {
+            label if label.is_empty() => " ".to_string(),
+            label => label,
+        };
+        let len = label.chars().count();
+        let left = tree.left(node).map(|l| Self::render(tree, &l));
+        let right = tree.right(node).map(|r| Self::render(tree, &r));
+        match (left, right) {
+            (None, None) => Self {
+                lines: vec![label],
+                width: len,
+                middle: len / 2,
+            },
+            (Some(left), None) => {
+                // Underscores run from the child's branch to the label
+                let first = format!(
+                    "{}{}{}",
+                    " ".repeat(left.middle + 1),
+                    "_".repeat(left.width - left.middle - 1),
+                    label
+                );
+                let second = format!(
+                    "{}/{}",
+                    " ".repeat(left.middle),
+                    " ".repeat(left.width - left.middle - 1 + len)
+                );
+                let below = left
+                    .lines
+                    .iter()
+                    .map(|l| format!("{}{}", l, " ".repeat(len)));
+                Self {
+                    lines: vec![first, second].into_iter().chain(below).collect(),
+                    width: left.width + len,
+                    middle: left.width + len / 2,
+                }
+            }
+            (None, Some(right)) => {
+                let first = format!(
+                    "{}{}{}",
+                    label,
+                    "_".repeat(right.middle),
+                    " ".repeat(right.width - right.middle)
+                );
+                let second = format!(
+                    "{}\\{}",
+                    " ".repeat(len + right.middle),
+                    " ".repeat(right.width - right.middle - 1)
+                );
+                let below = right
+                    .lines
+                    .iter()
+                    .map(|l| format!("{}{}", " ".repeat(len), l));
+                Self {
+                    lines: vec![first, second].into_iter().chain(below).collect(),
+                    width: len + right.width,
+                    middle: len / 2,
+                }
+            }
+            (Some(left), Some(right)) => {
+                let first = format!(
+                    "{}{}{}{}{}",
+                    " ".repeat(left.middle + 1),
+                    "_".repeat(left.width - left.middle - 1),
+                    label,
+                    "_".repeat(right.middle),
+                    " ".repeat(right.width - right.middle)
+                );
+                let second = format!(
+                    "{}/{}\\{}",
+                    " ".repeat(left.middle),
+                    " ".repeat(left.width - left.middle - 1 + len + right.middle),
+                    " ".repeat(right.width - right.middle - 1)
+                );
+                // Pad the shorter side with blank lines, then join side by side
+                let height = left.lines.len().max(right.lines.len());
+                let pad = |block: &Self, num: usize| {
+                    block
+                        .lines
+                        .get(num)
+                        .cloned()
+                        .unwrap_or_else(|| " ".repeat(block.width))
+                };
+                let below = (0..height).map(|num| {
+                    format!("{}{}{}", pad(&left, num), " ".repeat(len), pad(&right, num))
+                });
+                Self {
+                    lines: vec![first, second].into_iter().chain(below).collect(),
+                    width: left.width + len + right.width,
+                    middle: left.width + len / 2,
+                }
+            }
+        }
+    }
+}